@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{ParseError, PuzzleError};
+use crate::input::PuzzleConfiguration;
+
+///
+/// Known correct answers to the puzzles by year, day and part.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Answers{
+    answers: HashMap<(i32, i32, i32), String>,
+}
+
+impl Answers{
+    ///
+    /// Parses lines of the form `<year> <day> <part> <answer>`. Empty lines and lines starting with `#` are ignored.
+    ///
+    pub fn parse(text: &str) -> Result<Answers, ParseError>{
+        let mut answers = HashMap::new();
+        for (line_index, line) in text.lines().enumerate(){
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#'){
+                continue;
+            }
+            let mut items = line.splitn(4, char::is_whitespace);
+            let year = parse_item(items.next(), "year").map_err(|error| error.on_line(line_index))?;
+            let day = parse_item(items.next(), "day").map_err(|error| error.on_line(line_index))?;
+            let part = parse_item(items.next(), "part").map_err(|error| error.on_line(line_index))?;
+            let answer = items.next()
+                .map(str::trim)
+                .filter(|answer| !answer.is_empty())
+                .ok_or_else(|| ParseError::at(line_index, 0, "The answer is missing."))?;
+            if answers.insert((year, day, part), String::from(answer)).is_some(){
+                return Err(ParseError::at(line_index, 0, &format!("There is already an answer for day {} part {} of {}.", day, part, year)));
+            }
+        }
+        Ok(Answers {answers})
+    }
+
+    pub fn load(path: &Path) -> Result<Answers, PuzzleError>{
+        let text = fs::read_to_string(path)
+            .map_err(|error| PuzzleError::Io {source: path.display().to_string(), error})?;
+        Ok(Answers::parse(&text)?)
+    }
+
+    pub fn answer(&self, config: &PuzzleConfiguration) -> Option<&str>{
+        self.answers.get(&(config.year, config.day, config.part))
+            .map(String::as_str)
+    }
+}
+
+fn parse_item(maybe_item: Option<&str>, name: &str) -> Result<i32, ParseError>{
+    maybe_item.and_then(|item| item.parse::<i32>().ok())
+        .ok_or_else(|| ParseError::in_line(0, &format!("Expected the {} as a number.", name)))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict{
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl Verdict{
+    pub fn of(expected: Option<&str>, actual: &Result<String, PuzzleError>) -> Verdict{
+        match (expected, actual){
+            (None, _) => Verdict::Missing,
+            (Some(expected_answer), Ok(answer)) if expected_answer == answer => Verdict::Pass,
+            _ => Verdict::Fail,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self{
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail => write!(f, "FAIL"),
+            Verdict::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod answers_tests {
+    use super::*;
+    use rstest::rstest;
+    use crate::day;
+    use crate::input;
+    use crate::runner;
+    use crate::input_layout::{InputLayout, InputOptions};
+
+    ///
+    /// Puzzles whose solutions take too long to run with every test run.
+    ///
+    const SLOW_PUZZLES: [(i32, i32, i32); 1] = [(2020, 15, 2)];
+
+    #[test]
+    fn parse_answers() {
+        let text = "# year day part answer\n2020 1 1 928896\n\n2020 21 2 dtb,zgk,pxr\n";
+        let answers = Answers::parse(text).unwrap();
+        assert_eq!(answers.answer(&PuzzleConfiguration {year: 2020, day: 21, part: 2}), Some("dtb,zgk,pxr"));
+        assert_eq!(answers.answer(&PuzzleConfiguration {year: 2020, day: 1, part: 2}), None);
+        assert_eq!(answers.answer(&PuzzleConfiguration {year: 2019, day: 1, part: 1}), None);
+    }
+
+    #[rstest]
+    #[case("2020 1 1 928896\n2020 1 x 5", 2)]
+    #[case("2020 1 1\n", 1)]
+    #[case("1 1 5\n", 1)]
+    #[case("2020 1 1 5\n2020 1 1 6", 2)]
+    fn invalid_answers_are_reported_with_their_line(#[case] text: &str, #[case] expected_line: usize) {
+        let error = Answers::parse(text).unwrap_err();
+        assert_eq!(error.line, expected_line);
+    }
+
+    #[rstest]
+    #[case(Some("5"), Ok(String::from("5")), Verdict::Pass)]
+    #[case(Some("5"), Ok(String::from("6")), Verdict::Fail)]
+    #[case(Some("5"), Err(PuzzleError::unsolvable("No answer.")), Verdict::Fail)]
+    #[case(None, Ok(String::from("6")), Verdict::Missing)]
+    fn verdicts(#[case] expected: Option<&str>, #[case] actual: Result<String, PuzzleError>, #[case] expected_verdict: Verdict) {
+        assert_eq!(Verdict::of(expected, &actual), expected_verdict);
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(4)]
+    #[case(5)]
+    #[case(6)]
+    #[case(7)]
+    #[case(8)]
+    #[case(9)]
+    #[case(10)]
+    #[case(11)]
+    #[case(12)]
+    #[case(13)]
+    #[case(14)]
+    #[case(15)]
+    #[case(16)]
+    #[case(17)]
+    #[case(18)]
+    #[case(19)]
+    #[case(20)]
+    #[case(21)]
+    #[case(22)]
+    #[case(23)]
+    #[case(24)]
+    #[case(25)]
+    fn correct_answers(#[case] day: i32) {
+        let year = 2020;
+        let layout = InputLayout::resolve(&InputOptions::default()).unwrap();
+        let answers = Answers::load(&layout.answers_file).unwrap();
+        let configs: Vec<PuzzleConfiguration> = (1..=2)
+            .map(|part| PuzzleConfiguration {year, day, part})
+            .filter(|config| answers.answer(config).is_some() && !SLOW_PUZZLES.contains(&(config.year, config.day, config.part)))
+            .collect();
+        assert!(!configs.is_empty(), "There are no answers for day {}.", day);
+
+        for registration in day::variants(year, day){
+            let solver = registration.create_solver();
+            for config in &configs{
+                let problem_input = input::puzzle_input(&layout, config).unwrap();
+                let actual_result = runner::solve_part(solver.as_ref(), config.part, problem_input);
+                assert_eq!(actual_result.unwrap().answer, answers.answer(config).unwrap(), "Day {} part {} variant {}", config.day, config.part, registration.variant);
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::day::DaySolver;
+use crate::error::{ParseError, PuzzleError};
+use crate::input::PuzzleConfiguration;
+use crate::runner;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Statistics{
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Statistics{
+    ///
+    /// Computes the statistics of a non-empty set of samples.
+    /// The 95th percentile uses the nearest rank method.
+    ///
+    pub fn from_samples(samples: &[Duration]) -> Statistics{
+        assert!(!samples.is_empty(), "Statistics require at least one sample.");
+        let mut sorted_samples = samples.to_vec();
+        sorted_samples.sort_unstable();
+        let count = sorted_samples.len();
+
+        let median = (sorted_samples[(count - 1) / 2] + sorted_samples[count / 2]) / 2;
+        let mean = sorted_samples.iter().sum::<Duration>() / count as u32;
+        let p95_index = (count * 95 - 1) / 100;
+
+        Statistics {
+            min: sorted_samples[0],
+            median,
+            mean,
+            p95: sorted_samples[p95_index],
+        }
+    }
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "min {:?}, median {:?}, mean {:?}, p95 {:?}", self.min, self.median, self.mean, self.p95)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchResult{
+    pub config: PuzzleConfiguration,
+    pub parse: Statistics,
+    pub solve: Statistics,
+}
+
+///
+/// Solves the configured part of the puzzle `warmup_runs` times without measuring
+/// and then `runs` times, measuring parsing and solving separately.
+///
+pub fn benchmark(solver: &dyn DaySolver, config: &PuzzleConfiguration, input: &str, warmup_runs: usize, runs: usize) -> Result<BenchResult, PuzzleError>{
+    let solve = |input: String| runner::solve_part(solver, config.part, input);
+
+    for _ in 0..warmup_runs{
+        solve(input.to_owned())?;
+    }
+
+    let mut parse_times = Vec::with_capacity(runs);
+    let mut solve_times = Vec::with_capacity(runs);
+    for _ in 0..runs{
+        let timed_answer = solve(input.to_owned())?;
+        parse_times.push(timed_answer.parse_time);
+        solve_times.push(timed_answer.solve_time);
+    }
+
+    Ok(BenchResult {
+        config: *config,
+        parse: Statistics::from_samples(&parse_times),
+        solve: Statistics::from_samples(&solve_times),
+    })
+}
+
+///
+/// Median parse and solve times of earlier benchmarks by year, day and part.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Baseline{
+    entries: HashMap<(i32, i32, i32), BaselineEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaselineEntry{
+    pub parse_median: Duration,
+    pub solve_median: Duration,
+}
+
+const BASELINE_HEADER: &str = "# year day part parse_median_ns solve_median_ns";
+
+impl Baseline{
+    pub fn from_results(results: &[BenchResult]) -> Baseline{
+        let entries = results.iter()
+            .map(|result| ((result.config.year, result.config.day, result.config.part), BaselineEntry {parse_median: result.parse.median, solve_median: result.solve.median}))
+            .collect();
+        Baseline {entries}
+    }
+
+    pub fn entry(&self, config: &PuzzleConfiguration) -> Option<&BaselineEntry>{
+        self.entries.get(&(config.year, config.day, config.part))
+    }
+
+    pub fn parse(text: &str) -> Result<Baseline, ParseError>{
+        let mut entries = HashMap::new();
+        for (line_index, line) in text.lines().enumerate(){
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#'){
+                continue;
+            }
+            let values = line.split_whitespace()
+                .map(|value| value.parse::<u64>())
+                .collect::<Result<Vec<u64>, _>>()
+                .map_err(|_| ParseError::at(line_index, 0, "Expected five whole numbers."))?;
+            match values.as_slice(){
+                [year, day, part, parse_nanos, solve_nanos] => {
+                    let entry = BaselineEntry {parse_median: Duration::from_nanos(*parse_nanos), solve_median: Duration::from_nanos(*solve_nanos)};
+                    entries.insert((*year as i32, *day as i32, *part as i32), entry);
+                },
+                _ => return Err(ParseError::at(line_index, 0, "Expected five whole numbers.")),
+            }
+        }
+        Ok(Baseline {entries})
+    }
+
+    pub fn to_text(&self) -> String{
+        let mut keys: Vec<&(i32, i32, i32)> = self.entries.keys().collect();
+        keys.sort_unstable();
+        let mut lines = vec![String::from(BASELINE_HEADER)];
+        for key in keys{
+            let entry = &self.entries[key];
+            lines.push(format!("{} {} {} {} {}", key.0, key.1, key.2, entry.parse_median.as_nanos(), entry.solve_median.as_nanos()));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    pub fn load(path: &Path) -> Result<Baseline, PuzzleError>{
+        let text = fs::read_to_string(path)
+            .map_err(|error| PuzzleError::Io {source: path.display().to_string(), error})?;
+        Ok(Baseline::parse(&text)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), PuzzleError>{
+        fs::write(path, self.to_text())
+            .map_err(|error| PuzzleError::Io {source: path.display().to_string(), error})
+    }
+}
+
+///
+/// Median time that is slower than its baseline by more than the allowed threshold.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Regression{
+    pub phase: &'static str,
+    pub median: Duration,
+    pub baseline_median: Duration,
+}
+
+impl Regression{
+    pub fn slowdown_percent(&self) -> f64{
+        (self.median.as_secs_f64() / self.baseline_median.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} median {:?} is {:.1}% slower than the baseline {:?}", self.phase, self.median, self.slowdown_percent(), self.baseline_median)
+    }
+}
+
+pub fn regressions(result: &BenchResult, baseline_entry: &BaselineEntry, threshold_percent: u32) -> Vec<Regression>{
+    let phases = [
+        ("parse", result.parse.median, baseline_entry.parse_median),
+        ("solve", result.solve.median, baseline_entry.solve_median),
+    ];
+    phases.iter()
+        .filter(|(_, median, baseline_median)| is_regression(*median, *baseline_median, threshold_percent))
+        .map(|&(phase, median, baseline_median)| Regression {phase, median, baseline_median})
+        .collect()
+}
+
+fn is_regression(median: Duration, baseline_median: Duration, threshold_percent: u32) -> bool{
+    median.as_nanos() * 100 > baseline_median.as_nanos() * (100 + threshold_percent as u128)
+}
+
+
+
+#[cfg(test)]
+mod bench_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn milliseconds(values: &[u64]) -> Vec<Duration>{
+        values.iter()
+            .map(|value| Duration::from_millis(*value))
+            .collect()
+    }
+
+    #[test]
+    fn statistics_of_odd_number_of_samples() {
+        let samples = milliseconds(&[5, 1, 3, 9, 2]);
+        let expected_statistics = Statistics {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(3),
+            mean: Duration::from_millis(4),
+            p95: Duration::from_millis(9),
+        };
+        assert_eq!(Statistics::from_samples(&samples), expected_statistics);
+    }
+
+    #[test]
+    fn statistics_of_even_number_of_samples() {
+        let samples = milliseconds(&(1..=20).rev().collect::<Vec<u64>>());
+        let expected_statistics = Statistics {
+            min: Duration::from_millis(1),
+            median: Duration::from_micros(10500),
+            mean: Duration::from_micros(10500),
+            p95: Duration::from_millis(19),
+        };
+        assert_eq!(Statistics::from_samples(&samples), expected_statistics);
+    }
+
+    #[test]
+    fn baseline_roundtrip() {
+        let text = "# year day part parse_median_ns solve_median_ns\n2020 15 1 1200 5000000\n\n2020 23 2 800 90000000\n";
+        let baseline = Baseline::parse(text).unwrap();
+        let entry = baseline.entry(&PuzzleConfiguration {year: 2020, day: 23, part: 2}).unwrap();
+        assert_eq!(entry.solve_median, Duration::from_millis(90));
+        assert_eq!(baseline.to_text(), text.replace("\n\n", "\n"));
+    }
+
+    #[test]
+    fn invalid_baseline_line_is_reported() {
+        let error = Baseline::parse("2020 15 1 1200 5000000\n2020 15 2 x 3\n").unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+
+    #[rstest]
+    #[case(110, 0)]
+    #[case(111, 1)]
+    fn regressions_beyond_threshold(#[case] solve_millis: u64, #[case] expected_regressions: usize) {
+        let statistics = |millis| Statistics::from_samples(&milliseconds(&[millis]));
+        let result = BenchResult {config: PuzzleConfiguration {year: 2020, day: 15, part: 1}, parse: statistics(1), solve: statistics(solve_millis)};
+        let baseline_entry = BaselineEntry {parse_median: Duration::from_millis(1), solve_median: Duration::from_millis(100)};
+        assert_eq!(regressions(&result, &baseline_entry, 10).len(), expected_regressions);
+    }
+}
@@ -0,0 +1,722 @@
+use std::{collections::BTreeMap, convert::TryFrom, error::Error, fmt::Display, path::PathBuf};
+
+use crate::input::{InputSource, PuzzleConfiguration};
+use crate::input_layout::InputOptions;
+use crate::report::OutputFormat;
+
+pub const DEFAULT_YEAR: i32 = 2020;
+pub const FIRST_DAY: i32 = 1;
+pub const LAST_DAY: i32 = 25;
+
+pub const USAGE: &str =
+"Usage:
+    advent_of_code_2020 run <days>... [<puzzle options>] [--format <text|json|csv>] [--jobs <n>]
+    advent_of_code_2020 all [<puzzle options>] [--format <text|json|csv>] [--jobs <n>]
+    advent_of_code_2020 bench <days>... [<puzzle options>] [<benchmark options>]
+    advent_of_code_2020 verify <days>... [<puzzle options>] [--answers <file>]
+    advent_of_code_2020 list [<days>...] [--year <year>]
+    advent_of_code_2020 <day> <part>
+    advent_of_code_2020 help
+
+Days:
+    all             Every day from 1 to 25.
+    <n>             A single day, e.g. 7.
+    <a>..<b>        All days from a to b inclusive, e.g. 1..25.
+    Several day specifications can be given separated by spaces or commas.
+
+Puzzle options:
+    -y, --year <year>       The year of the puzzles. Defaults to 2020.
+    -p, --part <1|2|both>   The part(s) to solve. Defaults to both.
+    -i, --input <file>      Read the puzzle input from <file> instead of the input root.
+                            Use - to read from standard input. Requires a single day.
+                            Not available for verify.
+    --input-root <dir>      Read the puzzle inputs from <dir>/<year>/Day<n>.txt.
+                            Overrides AOC_INPUT_ROOT and the config file. Defaults to the
+                            Input folder of the repository.
+    --input-set <name>      Use the named input set of the config file, e.g. example or
+                            personal. Overrides AOC_INPUT_SET.
+    --config <file>         Read the input configuration from <file> instead of AOC_CONFIG
+                            or advent_of_code.conf in the working directory.
+    -v, --variant <day>=<variant>
+                            Use another variant of the solver for the day, e.g. day18=v1.
+                            Can be given several times. list shows the available variants.
+    -h, --help              Print this message.
+
+Output options:
+    -f, --format <format>   The output format. Defaults to text.
+                            json prints one JSON object per day and part, csv prints a CSV
+                            table; both report the answer, parse and solve time in
+                            nanoseconds, input and status.
+    -j, --jobs <n>          Solve up to <n> days and parts at the same time. The results are
+                            reported in the same order as with a single job. Defaults to 1.
+
+Benchmark options:
+    -n, --runs <n>              The number of measured runs per day and part. Defaults to 10.
+    -w, --warmup <n>            The number of unmeasured runs before measuring. Defaults to 3.
+    --baseline <file>           Compare the median times against a saved baseline.
+    --save-baseline <file>      Save the median times as a new baseline.
+    --threshold <percent>       How much slower than the baseline a median time may be before
+                                it is reported as a regression. Defaults to 10.
+
+Verification options:
+    -a, --answers <file>        Compare the answers against <file> instead of AOC_ANSWERS, the
+                                answers_file of the input set or Answers.txt next to the input root.
+                                Each line holds a year, a day, a part and the answer.
+
+Solving, benchmarking or verifying exits with status 1 if a puzzle fails or a regression is found or an
+answer is wrong.";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command{
+    Run(RunOptions),
+    Bench(BenchOptions),
+    Verify(VerifyOptions),
+    List(ListOptions),
+    Help,
+}
+
+///
+/// The puzzles a command works on and where their inputs and solvers come from.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleSelection{
+    pub year: i32,
+    pub days: Vec<i32>,
+    pub parts: Vec<i32>,
+    pub input_source: InputSource,
+    pub input: InputOptions,
+    pub variants: VariantSelection,
+}
+
+impl PuzzleSelection{
+    pub fn new(days: Vec<i32>) -> PuzzleSelection{
+        PuzzleSelection {
+            year: DEFAULT_YEAR,
+            days,
+            parts: vec![1, 2],
+            input_source: InputSource::PuzzleFile,
+            input: InputOptions::default(),
+            variants: VariantSelection::default(),
+        }
+    }
+
+    pub fn puzzle_configurations(&self) -> Vec<PuzzleConfiguration>{
+        self.days.iter()
+            .flat_map(|day| self.parts.iter()
+                .map(move |part| PuzzleConfiguration {year: self.year, day: *day, part: *part}))
+            .collect()
+    }
+
+    pub fn variant(&self, day: i32) -> Option<&str>{
+        self.variants.variant(day)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOptions{
+    pub puzzles: PuzzleSelection,
+    pub format: OutputFormat,
+    pub jobs: usize,
+}
+
+pub const DEFAULT_JOBS: usize = 1;
+
+pub const DEFAULT_WARMUP_RUNS: usize = 3;
+pub const DEFAULT_RUNS: usize = 10;
+pub const DEFAULT_REGRESSION_THRESHOLD_PERCENT: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchOptions{
+    pub puzzles: PuzzleSelection,
+    pub warmup_runs: usize,
+    pub runs: usize,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: Option<PathBuf>,
+    pub regression_threshold_percent: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyOptions{
+    pub puzzles: PuzzleSelection,
+    pub answers_file: Option<PathBuf>,
+}
+
+///
+/// * `year` - The year to list the solvers of; all years if `None`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListOptions{
+    pub year: Option<i32>,
+    pub days: Vec<i32>,
+}
+
+///
+/// Solver variants selected per day. Days without a selection use their default variant.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VariantSelection{
+    variants: BTreeMap<i32, String>,
+}
+
+impl VariantSelection{
+    pub fn variant(&self, day: i32) -> Option<&str>{
+        self.variants.get(&day)
+            .map(String::as_str)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UsageError{
+    pub message: String,
+}
+
+impl UsageError{
+    fn new(message: String) -> UsageError{
+        UsageError {message}
+    }
+}
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for UsageError {}
+
+///
+/// Parses the command line arguments, excluding the program name.
+///
+pub fn parse_arguments(args: &[String]) -> Result<Command, UsageError>{
+    if args.is_empty(){
+        return Err(UsageError::new(String::from("No command given.")));
+    }
+    match args[0].as_str(){
+        "run" => parse_run_arguments(&args[1..]),
+        "all" => parse_all_arguments(&args[1..]),
+        "bench" => parse_bench_arguments(&args[1..]),
+        "verify" => parse_verify_arguments(&args[1..]),
+        "list" => parse_list_arguments(&args[1..]),
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        _ => parse_legacy_arguments(args),
+    }
+}
+
+///
+/// Supports the original invocation with a positional day and part.
+///
+fn parse_legacy_arguments(args: &[String]) -> Result<Command, UsageError>{
+    if args.len() != 2 || args[0].parse::<i32>().is_err(){
+        return Err(UsageError::new(format!("Unknown command '{}'.", args[0])));
+    }
+    let day = parse_day(&args[0])?;
+    let parts = parse_parts(&args[1])?;
+    let puzzles = PuzzleSelection {parts, ..PuzzleSelection::new(vec![day])};
+    Ok(Command::Run(RunOptions {puzzles, format: OutputFormat::Text, jobs: DEFAULT_JOBS}))
+}
+
+///
+/// Days and option values given to a command, with short options replaced by their long form.
+///
+struct CommandArguments{
+    days: Vec<i32>,
+    options: Vec<(&'static str, String)>,
+}
+
+///
+/// Splits the arguments of a command into days and options. Returns `None` if help is requested.
+///
+fn split_command_arguments(args: &[String]) -> Result<Option<CommandArguments>, UsageError>{
+    let mut days: Vec<i32> = vec![];
+    let mut options: Vec<(&'static str, String)> = vec![];
+
+    let mut remaining_args = args.iter();
+    while let Some(arg) = remaining_args.next(){
+        match arg.as_str(){
+            "-h" | "--help" => return Ok(None),
+            option if option.starts_with('-') && option.len() > 1 => {
+                let name = long_option_name(option)
+                    .ok_or_else(|| UsageError::new(format!("Unknown option '{}'.", option)))?;
+                let value = option_value(arg, remaining_args.next())?;
+                if !is_repeatable(name) && options.iter().any(|(given_name, _)| *given_name == name){
+                    return Err(UsageError::new(format!("The option '{}' can only be given once.", arg)));
+                }
+                options.push((name, value.to_owned()));
+            },
+            day_specification => days.append(&mut parse_day_specification(day_specification)?),
+        }
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(Some(CommandArguments {days, options}))
+}
+
+fn long_option_name(option: &str) -> Option<&'static str>{
+    match option{
+        "-p" | "--part" => Some("--part"),
+        "-i" | "--input" => Some("--input"),
+        "-f" | "--format" => Some("--format"),
+        "-j" | "--jobs" => Some("--jobs"),
+        "-n" | "--runs" => Some("--runs"),
+        "-w" | "--warmup" => Some("--warmup"),
+        "--baseline" => Some("--baseline"),
+        "--save-baseline" => Some("--save-baseline"),
+        "--threshold" => Some("--threshold"),
+        "-a" | "--answers" => Some("--answers"),
+        "-v" | "--variant" => Some("--variant"),
+        "-y" | "--year" => Some("--year"),
+        "--input-root" => Some("--input-root"),
+        "--input-set" => Some("--input-set"),
+        "--config" => Some("--config"),
+        _ => None,
+    }
+}
+
+fn is_repeatable(option: &str) -> bool{
+    option == "--variant"
+}
+
+fn unavailable_option(option: &str, command: &str) -> UsageError{
+    UsageError::new(format!("The option '{}' is not available for {}.", option, command))
+}
+
+///
+/// Applies an option selecting the puzzles or their inputs. Returns whether the option is one of those.
+///
+fn apply_puzzle_option(puzzles: &mut PuzzleSelection, option: &str, value: &str) -> Result<bool, UsageError>{
+    match option{
+        "--year" => puzzles.year = parse_year(value)?,
+        "--part" => puzzles.parts = parse_parts(value)?,
+        "--input" => puzzles.input_source = parse_input_source(value),
+        "--input-root" => puzzles.input.input_root = Some(PathBuf::from(value)),
+        "--input-set" => puzzles.input.input_set = Some(String::from(value)),
+        "--config" => puzzles.input.config_file = Some(PathBuf::from(value)),
+        "--variant" => add_variant(&mut puzzles.variants, value)?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn check_puzzle_selection(puzzles: &PuzzleSelection) -> Result<(), UsageError>{
+    if puzzles.days.is_empty(){
+        return Err(UsageError::new(String::from("No days given to run.")));
+    }
+    if puzzles.input_source != InputSource::PuzzleFile && puzzles.days.len() > 1{
+        return Err(UsageError::new(String::from("A custom input can only be used when running a single day.")));
+    }
+    Ok(())
+}
+
+fn parse_run_arguments(args: &[String]) -> Result<Command, UsageError>{
+    let arguments = match split_command_arguments(args)?{
+        Some(arguments) => arguments,
+        None => return Ok(Command::Help),
+    };
+    run_command(arguments, "run")
+}
+
+///
+/// Runs every day, like `run all`.
+///
+fn parse_all_arguments(args: &[String]) -> Result<Command, UsageError>{
+    let arguments = match split_command_arguments(args)?{
+        Some(arguments) => arguments,
+        None => return Ok(Command::Help),
+    };
+    if !arguments.days.is_empty(){
+        return Err(UsageError::new(String::from("The command all does not take any days.")));
+    }
+    let all_days = CommandArguments {days: (FIRST_DAY..=LAST_DAY).collect(), options: arguments.options};
+    run_command(all_days, "all")
+}
+
+fn run_command(arguments: CommandArguments, command: &str) -> Result<Command, UsageError>{
+    let mut options = RunOptions {puzzles: PuzzleSelection::new(arguments.days), format: OutputFormat::Text, jobs: DEFAULT_JOBS};
+    for (option, value) in &arguments.options{
+        match *option{
+            "--format" => options.format = parse_format(value)?,
+            "--jobs" => options.jobs = parse_count(option, value, 1)?,
+            _ => if !apply_puzzle_option(&mut options.puzzles, option, value)?{
+                return Err(unavailable_option(option, command));
+            },
+        }
+    }
+    check_puzzle_selection(&options.puzzles)?;
+    Ok(Command::Run(options))
+}
+
+fn parse_bench_arguments(args: &[String]) -> Result<Command, UsageError>{
+    let arguments = match split_command_arguments(args)?{
+        Some(arguments) => arguments,
+        None => return Ok(Command::Help),
+    };
+    let mut options = BenchOptions {
+        puzzles: PuzzleSelection::new(arguments.days),
+        warmup_runs: DEFAULT_WARMUP_RUNS,
+        runs: DEFAULT_RUNS,
+        baseline: None,
+        save_baseline: None,
+        regression_threshold_percent: DEFAULT_REGRESSION_THRESHOLD_PERCENT,
+    };
+    for (option, value) in &arguments.options{
+        match *option{
+            "--runs" => options.runs = parse_count(option, value, 1)?,
+            "--warmup" => options.warmup_runs = parse_count(option, value, 0)?,
+            "--baseline" => options.baseline = Some(PathBuf::from(value)),
+            "--save-baseline" => options.save_baseline = Some(PathBuf::from(value)),
+            "--threshold" => options.regression_threshold_percent = parse_percent(option, value)?,
+            _ => if !apply_puzzle_option(&mut options.puzzles, option, value)?{
+                return Err(unavailable_option(option, "bench"));
+            },
+        }
+    }
+    check_puzzle_selection(&options.puzzles)?;
+    Ok(Command::Bench(options))
+}
+
+fn parse_verify_arguments(args: &[String]) -> Result<Command, UsageError>{
+    let arguments = match split_command_arguments(args)?{
+        Some(arguments) => arguments,
+        None => return Ok(Command::Help),
+    };
+    let mut options = VerifyOptions {puzzles: PuzzleSelection::new(arguments.days), answers_file: None};
+    for (option, value) in &arguments.options{
+        match *option{
+            "--answers" => options.answers_file = Some(PathBuf::from(value)),
+            "--input" => return Err(unavailable_option(option, "verify")),
+            _ => if !apply_puzzle_option(&mut options.puzzles, option, value)?{
+                return Err(unavailable_option(option, "verify"));
+            },
+        }
+    }
+    check_puzzle_selection(&options.puzzles)?;
+    Ok(Command::Verify(options))
+}
+
+fn parse_list_arguments(args: &[String]) -> Result<Command, UsageError>{
+    let arguments = match split_command_arguments(args)?{
+        Some(arguments) => arguments,
+        None => return Ok(Command::Help),
+    };
+    let mut year = None;
+    for (option, value) in &arguments.options{
+        match *option{
+            "--year" => year = Some(parse_year(value)?),
+            _ => return Err(unavailable_option(option, "list")),
+        }
+    }
+    let days = if arguments.days.is_empty(){
+        (FIRST_DAY..=LAST_DAY).collect()
+    } else {
+        arguments.days
+    };
+    Ok(Command::List(ListOptions {year, days}))
+}
+
+fn option_value<'a>(option: &str, maybe_value: Option<&'a String>) -> Result<&'a String, UsageError>{
+    maybe_value.ok_or_else(|| UsageError::new(format!("The option '{}' requires a value.", option)))
+}
+
+fn parse_day_specification(text: &str) -> Result<Vec<i32>, UsageError>{
+    let mut days = vec![];
+    for item in text.split(',').filter(|item| !item.is_empty()){
+        if item == "all"{
+            days.extend(FIRST_DAY..=LAST_DAY);
+        } else if let Some((start_text, end_text)) = split_range(item){
+            let start = parse_day(start_text)?;
+            let end = parse_day(end_text)?;
+            if start > end{
+                return Err(UsageError::new(format!("The day range '{}' is empty.", item)));
+            }
+            days.extend(start..=end);
+        } else {
+            days.push(parse_day(item)?);
+        }
+    }
+    Ok(days)
+}
+
+fn split_range(text: &str) -> Option<(&str, &str)>{
+    let separator_index = text.find("..")?;
+    let start_text = &text[..separator_index];
+    let end_text = &text[separator_index + 2..];
+    let inclusive_end_text = end_text.strip_prefix('=').unwrap_or(end_text);
+    Some((start_text, inclusive_end_text))
+}
+
+fn parse_day(text: &str) -> Result<i32, UsageError>{
+    match text.parse::<i32>(){
+        Ok(day) if (FIRST_DAY..=LAST_DAY).contains(&day) => Ok(day),
+        _ => Err(UsageError::new(format!("Invalid day '{}'. Days must be between {} and {}.", text, FIRST_DAY, LAST_DAY))),
+    }
+}
+
+///
+/// Advent of Code started in 2015.
+///
+fn parse_year(text: &str) -> Result<i32, UsageError>{
+    match text.parse::<i32>(){
+        Ok(year) if year >= 2015 => Ok(year),
+        _ => Err(UsageError::new(format!("Invalid year '{}'. Years start with 2015.", text))),
+    }
+}
+
+fn parse_parts(text: &str) -> Result<Vec<i32>, UsageError>{
+    match text{
+        "1" => Ok(vec![1]),
+        "2" => Ok(vec![2]),
+        "both" => Ok(vec![1, 2]),
+        _ => Err(UsageError::new(format!("Invalid part '{}'. The part must be 1, 2 or both.", text))),
+    }
+}
+
+fn parse_input_source(text: &str) -> InputSource{
+    match text{
+        "-" => InputSource::StandardInput,
+        path => InputSource::File(PathBuf::from(path)),
+    }
+}
+
+fn parse_count(option: &str, text: &str, minimum: usize) -> Result<usize, UsageError>{
+    match text.parse::<usize>(){
+        Ok(count) if count >= minimum => Ok(count),
+        _ => Err(UsageError::new(format!("Invalid value '{}' for '{}'. It must be a whole number of at least {}.", text, option, minimum))),
+    }
+}
+
+fn parse_percent(option: &str, text: &str) -> Result<u32, UsageError>{
+    let percent = parse_count(option, text, 0)?;
+    u32::try_from(percent)
+        .map_err(|_| UsageError::new(format!("Invalid value '{}' for '{}'. It must be at most {}.", text, option, u32::MAX)))
+}
+
+///
+/// Parses a variant selection of the form `day18=v1` or `18=v1`.
+///
+fn add_variant(variants: &mut VariantSelection, text: &str) -> Result<(), UsageError>{
+    let (day_text, variant) = match text.find('='){
+        Some(separator_index) => (&text[..separator_index], &text[separator_index + 1..]),
+        None => return Err(UsageError::new(format!("Invalid variant '{}'. Variants are selected as <day>=<variant>, e.g. day18=v1.", text))),
+    };
+    let day = parse_day(day_text.strip_prefix("day").unwrap_or(day_text))?;
+    if variant.is_empty(){
+        return Err(UsageError::new(format!("The variant for day {} is empty.", day)));
+    }
+    if variants.variants.insert(day, String::from(variant)).is_some(){
+        return Err(UsageError::new(format!("The variant for day {} can only be selected once.", day)));
+    }
+    Ok(())
+}
+
+fn parse_format(text: &str) -> Result<OutputFormat, UsageError>{
+    match text{
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        "csv" => Ok(OutputFormat::Csv),
+        _ => Err(UsageError::new(format!("Invalid format '{}'. The format must be text, json or csv.", text))),
+    }
+}
+
+
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn arguments(text: &str) -> Vec<String>{
+        text.split_whitespace()
+            .map(String::from)
+            .collect()
+    }
+
+    #[rstest]
+    #[case("run 5", vec![5])]
+    #[case("run 1..3", vec![1, 2, 3])]
+    #[case("run 1..=3", vec![1, 2, 3])]
+    #[case("run 3,1 7..8", vec![1, 3, 7, 8])]
+    #[case("run 2 2 1..2", vec![1, 2])]
+    fn days_to_run(#[case] command_line: &str, #[case] expected_days: Vec<i32>) {
+        let actual_command = parse_arguments(&arguments(command_line)).unwrap();
+        let expected_command = Command::Run(RunOptions {puzzles: PuzzleSelection::new(expected_days), format: OutputFormat::Text, jobs: DEFAULT_JOBS});
+        assert_eq!(actual_command, expected_command);
+    }
+
+    #[test]
+    fn run_all() {
+        let command = parse_arguments(&arguments("run all")).unwrap();
+        let expected_days: Vec<i32> = (1..=25).collect();
+        match command{
+            Command::Run(options) => assert_eq!(options.puzzles.days, expected_days),
+            _ => panic!("Expected a run command."),
+        }
+    }
+
+    #[rstest]
+    #[case("run 4 --part 1", vec![1])]
+    #[case("run 4 -p 2", vec![2])]
+    #[case("run 4 --part both", vec![1, 2])]
+    #[case("4 2", vec![2])]
+    fn parts_to_run(#[case] command_line: &str, #[case] expected_parts: Vec<i32>) {
+        let actual_command = parse_arguments(&arguments(command_line)).unwrap();
+        let puzzles = PuzzleSelection {parts: expected_parts, ..PuzzleSelection::new(vec![4])};
+        let expected_command = Command::Run(RunOptions {puzzles, format: OutputFormat::Text, jobs: DEFAULT_JOBS});
+        assert_eq!(actual_command, expected_command);
+    }
+
+    #[rstest]
+    #[case("run 9 --input other.txt", InputSource::File(PathBuf::from("other.txt")))]
+    #[case("run 9 -i -", InputSource::StandardInput)]
+    fn custom_input(#[case] command_line: &str, #[case] expected_source: InputSource) {
+        let actual_command = parse_arguments(&arguments(command_line)).unwrap();
+        let puzzles = PuzzleSelection {input_source: expected_source, ..PuzzleSelection::new(vec![9])};
+        let expected_command = Command::Run(RunOptions {puzzles, format: OutputFormat::Text, jobs: DEFAULT_JOBS});
+        assert_eq!(actual_command, expected_command);
+    }
+
+    #[rstest]
+    #[case("run 9 --format json", OutputFormat::Json)]
+    #[case("run 9 -f csv", OutputFormat::Csv)]
+    #[case("run 9 --format text", OutputFormat::Text)]
+    fn output_format(#[case] command_line: &str, #[case] expected_format: OutputFormat) {
+        let actual_command = parse_arguments(&arguments(command_line)).unwrap();
+        let expected_command = Command::Run(RunOptions {puzzles: PuzzleSelection::new(vec![9]), format: expected_format, jobs: DEFAULT_JOBS});
+        assert_eq!(actual_command, expected_command);
+    }
+
+    #[test]
+    fn bench_defaults() {
+        let actual_command = parse_arguments(&arguments("bench 15 --part 2")).unwrap();
+        let expected_options = BenchOptions {
+            puzzles: PuzzleSelection {parts: vec![2], ..PuzzleSelection::new(vec![15])},
+            warmup_runs: DEFAULT_WARMUP_RUNS,
+            runs: DEFAULT_RUNS,
+            baseline: None,
+            save_baseline: None,
+            regression_threshold_percent: DEFAULT_REGRESSION_THRESHOLD_PERCENT,
+        };
+        assert_eq!(actual_command, Command::Bench(expected_options));
+    }
+
+    #[test]
+    fn bench_options() {
+        let command_line = "bench 15,23 -n 20 -w 0 --baseline old.txt --save-baseline new.txt --threshold 25";
+        let actual_command = parse_arguments(&arguments(command_line)).unwrap();
+        let expected_options = BenchOptions {
+            puzzles: PuzzleSelection::new(vec![15, 23]),
+            warmup_runs: 0,
+            runs: 20,
+            baseline: Some(PathBuf::from("old.txt")),
+            save_baseline: Some(PathBuf::from("new.txt")),
+            regression_threshold_percent: 25,
+        };
+        assert_eq!(actual_command, Command::Bench(expected_options));
+    }
+
+    #[rstest]
+    #[case("verify all", None)]
+    #[case("verify all --answers mine.txt", Some(PathBuf::from("mine.txt")))]
+    fn verify_options(#[case] command_line: &str, #[case] expected_answers_file: Option<PathBuf>) {
+        let actual_command = parse_arguments(&arguments(command_line)).unwrap();
+        let expected_command = Command::Verify(VerifyOptions {puzzles: PuzzleSelection::new((1..=25).collect()), answers_file: expected_answers_file});
+        assert_eq!(actual_command, expected_command);
+    }
+
+    #[rstest]
+    #[case("run 18 --variant day18=v1", vec![(18, "v1")])]
+    #[case("run 18,20 -v 18=v1 -v day20=fast", vec![(18, "v1"), (20, "fast")])]
+    fn variants(#[case] command_line: &str, #[case] expected_variants: Vec<(i32, &str)>) {
+        let command = parse_arguments(&arguments(command_line)).unwrap();
+        let options = match command{
+            Command::Run(options) => options,
+            _ => panic!("Expected a run command."),
+        };
+        for (day, variant) in expected_variants{
+            assert_eq!(options.puzzles.variant(day), Some(variant));
+        }
+        assert_eq!(options.puzzles.variant(1), None);
+    }
+
+    #[test]
+    fn year_and_input_root() {
+        let actual_command = parse_arguments(&arguments("run 3 --year 2019 --input-root /data/aoc")).unwrap();
+        let input = InputOptions {input_root: Some(PathBuf::from("/data/aoc")), ..InputOptions::default()};
+        let puzzles = PuzzleSelection {year: 2019, input, ..PuzzleSelection::new(vec![3])};
+        assert_eq!(actual_command, Command::Run(RunOptions {puzzles, format: OutputFormat::Text, jobs: DEFAULT_JOBS}));
+    }
+
+    #[test]
+    fn input_set_and_config_file() {
+        let actual_command = parse_arguments(&arguments("verify 3 --input-set example --config aoc.conf")).unwrap();
+        let input = InputOptions {input_set: Some(String::from("example")), config_file: Some(PathBuf::from("aoc.conf")), ..InputOptions::default()};
+        let puzzles = PuzzleSelection {input, ..PuzzleSelection::new(vec![3])};
+        assert_eq!(actual_command, Command::Verify(VerifyOptions {puzzles, answers_file: None}));
+    }
+
+    #[test]
+    fn puzzle_configurations_use_the_year() {
+        let puzzles = PuzzleSelection {year: 2019, parts: vec![2], ..PuzzleSelection::new(vec![3, 4])};
+        let expected_configs = vec![
+            PuzzleConfiguration {year: 2019, day: 3, part: 2},
+            PuzzleConfiguration {year: 2019, day: 4, part: 2},
+        ];
+        assert_eq!(puzzles.puzzle_configurations(), expected_configs);
+    }
+
+    #[rstest]
+    #[case("all", DEFAULT_JOBS)]
+    #[case("all --jobs 4", 4)]
+    #[case("run all -j 8", 8)]
+    fn jobs(#[case] command_line: &str, #[case] expected_jobs: usize) {
+        let actual_command = parse_arguments(&arguments(command_line)).unwrap();
+        let puzzles = PuzzleSelection::new((1..=25).collect());
+        assert_eq!(actual_command, Command::Run(RunOptions {puzzles, format: OutputFormat::Text, jobs: expected_jobs}));
+    }
+
+    #[rstest]
+    #[case("list", None, (1..=25).collect())]
+    #[case("list 18 20 --year 2020", Some(2020), vec![18, 20])]
+    fn days_to_list(#[case] command_line: &str, #[case] expected_year: Option<i32>, #[case] expected_days: Vec<i32>) {
+        let actual_command = parse_arguments(&arguments(command_line)).unwrap();
+        assert_eq!(actual_command, Command::List(ListOptions {year: expected_year, days: expected_days}));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("run")]
+    #[case("run 0")]
+    #[case("run 26")]
+    #[case("run 5..3")]
+    #[case("run x")]
+    #[case("run 5 --part 3")]
+    #[case("run 5 --part")]
+    #[case("run 5 --verbose")]
+    #[case("run 5 --format xml")]
+    #[case("run 5 -f json -f csv")]
+    #[case("run 1..2 --input other.txt")]
+    #[case("run 5 --runs 3")]
+    #[case("bench 5 --format json")]
+    #[case("bench 5 --jobs 2")]
+    #[case("all 5")]
+    #[case("all --jobs 0")]
+    #[case("bench 5 --runs 0")]
+    #[case("bench 5 --warmup -1")]
+    #[case("bench 5 --threshold 4294967296")]
+    #[case("bench")]
+    #[case("verify 5 --input other.txt")]
+    #[case("run 18 --variant v1")]
+    #[case("run 18 --variant day18=")]
+    #[case("run 18 --variant day26=v1")]
+    #[case("run 18 -v day18=v1 -v 18=v2")]
+    #[case("list 18 --part 1")]
+    #[case("run 5 --year 1999")]
+    #[case("run 5 --year twenty")]
+    #[case("5")]
+    #[case("5 3")]
+    #[case("frobnicate 5")]
+    fn invalid_arguments(#[case] command_line: &str) {
+        let result = parse_arguments(&arguments(command_line));
+        assert!(result.is_err());
+    }
+}
@@ -1,80 +1,214 @@
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day18_v2;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-
-
-pub trait DaySolver{
-    fn solve_part1(&self, input: String) -> String;
-    fn solve_part2(&self, input: String) -> String;
+///
+/// Declares the day modules and collects the solvers they register.
+/// Every module provides a `REGISTRATION` describing its solver.
+///
+macro_rules! register_solvers {
+    ($($module:ident),* $(,)?) => {
+        $(mod $module;)*
+
+        pub fn registrations() -> Vec<&'static SolverRegistration>{
+            vec![$(&$module::REGISTRATION),*]
+        }
+    };
+}
+
+register_solvers!(
+    day1,
+    day2,
+    day3,
+    day4,
+    day5,
+    day6,
+    day7,
+    day8,
+    day9,
+    day10,
+    day11,
+    day11_v2,
+    day12,
+    day13,
+    day14,
+    day15,
+    day16,
+    day17,
+    day17_v2,
+    day18,
+    day18_v2,
+    day19,
+    day20,
+    day21,
+    day22,
+    day23,
+    day24,
+    day25,
+);
+
+
+use std::time::{Duration, Instant};
+
+use crate::error::{ParseError, PuzzleError};
+
+///
+/// Solvers are shared between threads when several puzzles are solved at the same time,
+/// so they must not keep state between solving puzzles.
+///
+pub trait DaySolver: Send + Sync{
+    fn solve_part1(&self, input: String) -> Result<String, PuzzleError>{
+        self.solve_part1_timed(input).map(|timed_answer| timed_answer.answer)
+    }
+
+    fn solve_part2(&self, input: String) -> Result<String, PuzzleError>{
+        self.solve_part2_timed(input).map(|timed_answer| timed_answer.answer)
+    }
+
+    fn solve_part1_timed(&self, input: String) -> Result<TimedAnswer, PuzzleError>;
+    fn solve_part2_timed(&self, input: String) -> Result<TimedAnswer, PuzzleError>;
 }
 
-pub trait Day{
+///
+/// Answer to a puzzle part together with the time spent on parsing the input and on solving the puzzle.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedAnswer{
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+pub trait Day: Send + Sync{
     type PuzzleInput;
-    fn parse_input(&self, input: String) -> Self::PuzzleInput;
-    fn solve_part1(&self, input: Self::PuzzleInput) -> String;
-    fn solve_part2(&self, input: Self::PuzzleInput) -> String;
+    fn parse_input(&self, input: String) -> Result<Self::PuzzleInput, PuzzleError>;
+    fn solve_part1(&self, input: Self::PuzzleInput) -> Result<String, PuzzleError>;
+    fn solve_part2(&self, input: Self::PuzzleInput) -> Result<String, PuzzleError>;
 }
 
 impl<T> DaySolver for T where T: Day {
-    fn solve_part1(&self, input: String) -> String {
-        self.solve_part1(self.parse_input(input))
+    fn solve_part1_timed(&self, input: String) -> Result<TimedAnswer, PuzzleError> {
+        solve_timed(|| self.parse_input(input), |puzzle_input| self.solve_part1(puzzle_input))
     }
 
-    fn solve_part2(&self, input: String) -> String {
-        self.solve_part2(self.parse_input(input))
+    fn solve_part2_timed(&self, input: String) -> Result<TimedAnswer, PuzzleError> {
+        solve_timed(|| self.parse_input(input), |puzzle_input| self.solve_part2(puzzle_input))
     }
 }
 
-pub fn get_day(&day: &i32) -> Option<Box<dyn DaySolver>>{
-    match day{
-        1 => Some(Box::new(day1::Day1 {})),
-        2 => Some(Box::new(day2::Day2 {})),
-        3 => Some(Box::new(day3::Day3 {})),
-        4 => Some(Box::new(day4::Day4 {})),
-        5 => Some(Box::new(day5::Day5 {})),
-        6 => Some(Box::new(day6::Day6 {})),
-        7 => Some(Box::new(day7::Day7 {})),
-        8 => Some(Box::new(day8::Day8 {})),
-        9 => Some(Box::new(day9::Day9 {})),
-        10 => Some(Box::new(day10::Day10 {})),
-        11 => Some(Box::new(day11::Day11 {})),
-        12 => Some(Box::new(day12::Day12 {})),
-        13 => Some(Box::new(day13::Day13 {})),
-        14 => Some(Box::new(day14::Day14 {})),
-        15 => Some(Box::new(day15::Day15 {})),
-        16 => Some(Box::new(day16::Day16 {})),
-        17 => Some(Box::new(day17::Day17 {})),
-        18 => Some(Box::new(day18_v2::Day18 {})),
-        19 => Some(Box::new(day19::Day19 {})),
-        20 => Some(Box::new(day20::Day20 {})),
-        21 => Some(Box::new(day21::Day21 {})),
-        22 => Some(Box::new(day22::Day22 {})),
-        23 => Some(Box::new(day23::Day23 {})),
-        24 => Some(Box::new(day24::Day24 {})),
-        25 => Some(Box::new(day25::Day25 {})),
-        _ => None
+fn solve_timed<I>(parse: impl FnOnce() -> Result<I, PuzzleError>, solve: impl FnOnce(I) -> Result<String, PuzzleError>) -> Result<TimedAnswer, PuzzleError>{
+    let parse_start = Instant::now();
+    let puzzle_input = parse()?;
+    let parse_time = parse_start.elapsed();
+
+    let solve_start = Instant::now();
+    let answer = solve(puzzle_input)?;
+    let solve_time = solve_start.elapsed();
+
+    Ok(TimedAnswer {answer, parse_time, solve_time})
+}
+
+///
+/// Parses each non-empty line of the text and attaches the line number to parse errors.
+///
+pub fn parse_lines<T>(text: &str, parse_line: impl Fn(&str) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError>{
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_index, line)| parse_line(line).map_err(|error| error.on_line(line_index)))
+        .collect()
+}
+
+///
+/// Parses a number in a line and reports the zero-based column index of the text on failure.
+///
+pub fn parse_number<T: std::str::FromStr>(text: &str, column_index: usize) -> Result<T, ParseError>{
+    text.parse::<T>()
+        .map_err(|_| ParseError::in_line(column_index, &format!("'{}' is not a valid number.", text)))
+}
+
+///
+/// Describes a solver for a puzzle and how to create it.
+///
+/// * `variant` - Label distinguishing alternative implementations for the same day, e.g. `v1` or `v2`.
+/// * `is_default` - Whether this variant is used if no variant is selected explicitly.
+///
+pub struct SolverRegistration{
+    pub year: i32,
+    pub day: i32,
+    pub name: &'static str,
+    pub variant: &'static str,
+    pub is_default: bool,
+    pub create: fn() -> Box<dyn DaySolver>,
+}
+
+impl SolverRegistration{
+    pub fn create_solver(&self) -> Box<dyn DaySolver>{
+        (self.create)()
+    }
+}
+
+///
+/// Finds the solver registered for the day, using the default variant if no variant is given.
+///
+pub fn find_registration(year: i32, day: i32, maybe_variant: Option<&str>) -> Result<&'static SolverRegistration, PuzzleError>{
+    let mut candidates = variants(year, day).into_iter();
+    match maybe_variant{
+        None => candidates.find(|registration| registration.is_default)
+            .ok_or(PuzzleError::UnknownDay {year, day}),
+        Some(variant) => candidates.find(|registration| registration.variant == variant)
+            .ok_or_else(|| PuzzleError::UnknownVariant {year, day, variant: String::from(variant)}),
+    }
+}
+
+///
+/// All registered variants for the day of the year.
+///
+pub fn variants(year: i32, day: i32) -> Vec<&'static SolverRegistration>{
+    registrations().into_iter()
+        .filter(|registration| registration.year == year && registration.day == day)
+        .collect()
+}
+
+///
+/// The years with at least one registered solver, in ascending order.
+///
+pub fn years() -> Vec<i32>{
+    let mut years: Vec<i32> = registrations().into_iter()
+        .map(|registration| registration.year)
+        .collect();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
+
+
+
+#[cfg(test)]
+mod day_tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_day_has_exactly_one_default_variant() {
+        for day in 1..=25{
+            let default_count = variants(2020, day).iter()
+                .filter(|registration| registration.is_default)
+                .count();
+            assert_eq!(default_count, 1, "Day {}", day);
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn variants_are_unique() {
+        let mut seen_variants = HashSet::new();
+        for registration in registrations(){
+            let is_new = seen_variants.insert((registration.year, registration.day, registration.variant));
+            assert!(is_new, "Day {} variant {} is registered twice.", registration.day, registration.variant);
+        }
+    }
+
+    #[test]
+    fn variants_can_be_selected() {
+        assert_eq!(find_registration(2020, 18, Some("v1")).unwrap().variant, "v1");
+        assert_eq!(find_registration(2020, 18, None).unwrap().variant, "v2");
+        assert!(find_registration(2020, 18, Some("v3")).is_err());
+        assert!(find_registration(2019, 18, None).is_err());
+    }
+}
@@ -1,30 +1,40 @@
 
 use std::ops::Add;
 
+use crate::error::PuzzleError;
+use super::{parse_lines, parse_number};
+
 pub struct Day1 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 1,
+    name: "Report Repair",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day1 {}),
+};
+
 impl super::Day for Day1{
     type PuzzleInput = Vec<i32>;
-    fn parse_input(&self, input: String) -> Vec<i32> {
-        input.lines()
-            .map(|line| line.parse::<i32>())
-            .filter_map(Result::ok)
-            .collect()
+    fn parse_input(&self, input: String) -> Result<Vec<i32>, PuzzleError> {
+        let numbers = parse_lines(&input, |line| parse_number::<i32>(line, 0))?;
+        Ok(numbers)
     }
 
-    fn solve_part1(&self, input: Vec<i32>) -> String {
+    fn solve_part1(&self, input: Vec<i32>) -> Result<String, PuzzleError> {
         let summing_pair: Option<(i32, i32)> = find_summing_pair(&input, &2020);
         match summing_pair{
-            Some((l,h)) => (l * h).to_string(),
-            None => String::from("No matching pair found.")
+            Some((l,h)) => Ok((l * h).to_string()),
+            None => Err(PuzzleError::unsolvable("No matching pair found."))
         }
     }
 
-    fn solve_part2(&self, input: Vec<i32>) -> String {
+    fn solve_part2(&self, input: Vec<i32>) -> Result<String, PuzzleError> {
         let summing_triple: Option<(i32, i32, i32)> = find_summing_triple(input, 2020);
         match summing_triple{
-            Some((l, m, h)) => (l * m * h).to_string(),
-            None => String::from("No matching triple found.")
+            Some((l, m, h)) => Ok((l * m * h).to_string()),
+            None => Err(PuzzleError::unsolvable("No matching triple found."))
         }
     }
 }
@@ -90,7 +100,6 @@ fn find_summing_triple_in_sorted(sorted_candidates: &[i32], desired_sum: i32) ->
 #[cfg(test)]
 mod day1_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -107,7 +116,7 @@ mod day1_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day1{});
         let problem_input = example_input();
         let expected_result = String::from("514579");
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -116,24 +125,7 @@ mod day1_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day1{});
         let problem_input = example_input();
         let expected_result = String::from("241861950");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day1{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 1, part: 1}).unwrap();
-        let expected_result = String::from("928896");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day1{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 1, part: 2}).unwrap();
-        let expected_result = String::from("295668576");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
@@ -1,19 +1,30 @@
-
+use crate::error::PuzzleError;
+use super::{parse_lines, parse_number};
 
 pub struct Day10 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 10,
+    name: "Adapter Array",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day10 {}),
+};
+
 impl super::Day for Day10{
     type PuzzleInput = Vec<usize>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .map(|line| line.parse::<usize>())
-            .filter_map(Result::ok)
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let adapters = parse_lines(&text, |line| parse_number::<usize>(line, 0))?;
+        Ok(adapters)
     }
 
-    fn solve_part1(&self, mut adapters: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, mut adapters: Self::PuzzleInput) -> Result<String, PuzzleError> {
         adapters.sort_unstable();
+        if !is_chainable(&adapters){
+            return Err(PuzzleError::unsolvable("The adapters cannot be chained."));
+        }
         let mut joltage_gaps = [0, 0, 0];
         joltage_gaps[adapters[0]-1] += 1;
         for pair in adapters.windows(2){
@@ -22,16 +33,26 @@ impl super::Day for Day10{
         }
         joltage_gaps[3-1] += 1;
         let result = joltage_gaps[0] * joltage_gaps[3-1];
-        result.to_string()
+        Ok(result.to_string())
     }
 
-    fn solve_part2(&self, mut adapters: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, mut adapters: Self::PuzzleInput) -> Result<String, PuzzleError> {
         adapters.sort_unstable();
+        if !is_chainable(&adapters){
+            return Err(PuzzleError::unsolvable("The adapters cannot be chained."));
+        }
         let result = number_of_possible_adapter_combinations(&adapters);
-        result.to_string()
+        Ok(result.to_string())
     }
 }
 
+fn is_chainable(sorted_adapters: &[usize]) -> bool{
+    !sorted_adapters.is_empty()
+        && (1..=3).contains(&sorted_adapters[0])
+        && sorted_adapters.windows(2)
+            .all(|pair| (1..=3).contains(&(pair[1] - pair[0])))
+}
+
 fn number_of_possible_adapter_combinations(sorted_adapters: &[usize]) -> usize{
     if sorted_adapters.is_empty(){
         return 1;
@@ -75,7 +96,6 @@ fn combinations_by_previous_gap(prior_combinations_by_previous_gap: [usize; 3],
 #[cfg(test)]
 mod day10_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -133,7 +153,7 @@ mod day10_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day10{});
         let problem_input = example_input();
         let expected_result = (7 * 5).to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -142,7 +162,7 @@ mod day10_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day10{});
         let problem_input = example2_input();
         let expected_result = (22 * 10).to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -151,7 +171,7 @@ mod day10_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day10{});
         let problem_input = example_input();
         let expected_result = 8.to_string();
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -160,25 +180,7 @@ mod day10_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day10{});
         let problem_input = example2_input();
         let expected_result = 19208.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day10{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 10, part: 1}).unwrap();
-        let expected_result = String::from("2368");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day10{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 10, part: 2}).unwrap();
-        let expected_result = String::from("1727094849536");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
@@ -1,10 +1,8 @@
 
-use std::convert::TryFrom;
-use std::iter;
-
-use crate::grid::{self, Grid};
-use crate::space::{self, Point, Vector};
-use crate::game_of_life;
+use crate::grid;
+use crate::space::{Neighbourhood, Point};
+use crate::game_of_life::{self, LifeLikeRule, LifeLikeRules};
+use crate::error::PuzzleError;
 
 
 #[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
@@ -20,192 +18,83 @@ impl Default for SeatState{
     }
 }
 
-impl From<char> for SeatState{
-    fn from(character: char) -> Self {
-        match character{
-            'L' => SeatState::Empty,
-            '#' => SeatState::Occupied,
-            _ => SeatState::default(),
-        }
-    }
+pub(super) fn seat_codec() -> grid::TextCodec<SeatState>{
+    grid::TextCodec::new(vec![('L', SeatState::Empty), ('#', SeatState::Occupied), ('.', SeatState::Floor)])
 }
 
 
 pub struct Day11 {}
 
-impl super::Day for Day11{
-    type PuzzleInput = grid::OutsideDefaultGrid<i128, SeatState, 2>;
-
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        if text.is_empty(){
-            return default_grid();
-        }
-
-        let seat_rows: Vec<&str> = text.lines().collect();
-        let maybe_row_count = i128::try_from(seat_rows.len());
-        if maybe_row_count.is_err(){
-            return default_grid();
-        }
-        let row_count = maybe_row_count.unwrap();
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 11,
+    name: "Seating System",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day11 {}),
+};
 
-        let maybe_column_count = i128::try_from(seat_rows[0].len());
-        if maybe_column_count.is_err(){
-            return default_grid();
-        }
-        let column_count = maybe_column_count.unwrap();
-        if column_count == 0{
-            return default_grid();
-        }
+impl super::Day for Day11{
+    type PuzzleInput = grid::DenseGrid<i128, SeatState, 2>;
 
-        let known_seats = seat_rows.iter()
-            .enumerate()
-            .map(|(row, line)| line.chars()
-                .map(SeatState::from)
-                .enumerate()
-                .filter_map(move |(column, seat)| {
-                    let maybe_column = i128::try_from(column);
-                    let maybe_row = i128::try_from(row);
-                    if maybe_column.is_err() || maybe_row.is_err(){
-                        return None;
-                    }
-                    let coordinate_x = maybe_column.unwrap();
-                    let coordinate_y = maybe_row.unwrap();
-                    Some((Point::new([coordinate_x, coordinate_y]), seat))
-                }))
-            .flatten();
-        let coordinate_ranges = [
-            grid::CoordinateRange {lower_bound: 0, upper_bound: column_count},
-            grid::CoordinateRange {lower_bound: 0, upper_bound: row_count}
-        ];
-        grid::OutsideDefaultGrid::new(coordinate_ranges, SeatState::default(), known_seats)
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let seating_area = seat_codec().parse(&text, SeatState::default(), grid::OutsideBehaviour::Default)?;
+        Ok(seating_area)
     }
 
-    fn solve_part1(&self, seating_area: Self::PuzzleInput) -> std::string::String {
-        let seating_bounds = seating_area.coordinate_ranges();
-        let seats: Vec<Point<i128, 2>> = (seating_bounds[0].lower_bound..seating_bounds[0].upper_bound)
-            .map(|x| (seating_bounds[1].lower_bound..seating_bounds[1].upper_bound)
-                .map(move |y| Point::new([x,y])))
-            .flatten()
-            .filter(|point| match seating_area.at_point(point){
-                    SeatState::Floor => false,
-                    SeatState::Empty => true,
-                    SeatState::Occupied => true,
-                })
-            .collect();
-        let initially_occupied_seats: Vec<Point<i128, 2>> = seats.iter()
-            .filter(|point| match seating_area.at_point(point){
-                SeatState::Floor => false,
-                SeatState::Empty => false,
-                SeatState::Occupied => true,
-            })
-            .map(|point| point.to_owned())
-            .collect();
-        let game_of_life_rules = SimpleSeatingRules {seating_area};
-        let game_of_life_runner = game_of_life::GameOfLife::new(game_of_life_rules);
-        let stabelized_occupied_seats = game_of_life_runner.active_items_after_stabelizing(initially_occupied_seats.iter(), Some(seats.iter()));
-        let number_of_occupied_seats = stabelized_occupied_seats.count();
-        number_of_occupied_seats.to_string()
+    fn solve_part1(&self, seating_area: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        let seating_rule = LifeLikeRule::parse(ADJACENT_SEATING_RULE)?;
+        let number_of_occupied_seats = stabelized_occupied_seat_count(&seating_area, Neighbourhood::moore(), seating_rule);
+        Ok(number_of_occupied_seats.to_string())
     }
 
-    fn solve_part2(&self, seating_area: Self::PuzzleInput) -> std::string::String {
-        let seating_bounds = seating_area.coordinate_ranges();
-        let seats: Vec<Point<i128, 2>> = (seating_bounds[0].lower_bound..seating_bounds[0].upper_bound)
-            .map(|x| (seating_bounds[1].lower_bound..seating_bounds[1].upper_bound)
-                .map(move |y| Point::new([x,y])))
-            .flatten()
-            .filter(|point| match seating_area.at_point(point){
-                    SeatState::Floor => false,
-                    SeatState::Empty => true,
-                    SeatState::Occupied => true,
-                })
-            .collect();
-        let initially_occupied_seats: Vec<Point<i128, 2>> = seats.iter()
-            .filter(|point| match seating_area.at_point(point){
-                SeatState::Floor => false,
-                SeatState::Empty => false,
-                SeatState::Occupied => true,
-            })
-            .map(|point| point.to_owned())
-            .collect();
-        let game_of_life_rules = AdvancesSeatingRules {seating_area};
-        let game_of_life_runner = game_of_life::GameOfLife::new(game_of_life_rules);
-        let stabelized_occupied_seats = game_of_life_runner.active_items_after_stabelizing(initially_occupied_seats.iter(), Some(seats.iter()));
-        let number_of_occupied_seats = stabelized_occupied_seats.count();
-        number_of_occupied_seats.to_string()
+    fn solve_part2(&self, seating_area: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        let seating_rule = LifeLikeRule::parse(VISIBLE_SEATING_RULE)?;
+        let number_of_occupied_seats = stabelized_occupied_seat_count(&seating_area, Neighbourhood::moore().line_of_sight(), seating_rule);
+        Ok(number_of_occupied_seats.to_string())
     }
 }
 
-fn default_grid() -> grid::OutsideDefaultGrid<i128, SeatState, 2>{
-    let coordinate_ranges: [grid::CoordinateRange<i128>; 2] = [
-        grid::CoordinateRange::default(),
-        grid::CoordinateRange::default()
-    ];
-    grid::OutsideDefaultGrid::<i128, SeatState, 2>::new(coordinate_ranges, SeatState::default(), iter::empty::<(Point<i128, 2>, SeatState)>())
-}
-
-struct SimpleSeatingRules<T>{
-    seating_area: T,
+///
+/// Empty seats become occupied if no adjacent seat is occupied, and occupied seats become empty
+/// if at least 4 adjacent seats are occupied.
+///
+const ADJACENT_SEATING_RULE: &str = "B0/S0123";
+///
+/// Empty seats become occupied if no visible seat is occupied, and occupied seats become empty
+/// if at least 5 visible seats are occupied.
+///
+const VISIBLE_SEATING_RULE: &str = "B0/S01234";
+
+///
+/// The seats considered as neighbours are determined by the neighbourhood, e.g. the adjacent seats
+/// or the first seats visible in each direction.
+///
+fn stabelized_occupied_seat_count<T: grid::Grid<SeatState, 2, CoordinateType=i128>>(seating_area: &T, neighbourhood: Neighbourhood<i128, 2>, seating_rule: LifeLikeRule) -> usize{
+    let seats: Vec<Point<i128, 2>> = seating_area.bounding_box()
+        .points()
+        .filter(|point| is_seat(seating_area, point))
+        .collect();
+    let initially_occupied_seats: Vec<Point<i128, 2>> = seats.iter()
+        .filter(|point| seating_area.at_point(point) == &SeatState::Occupied)
+        .map(|point| point.to_owned())
+        .collect();
+    let game_of_life_rules = LifeLikeRules::new(seating_rule, |seat: &Point<i128, 2>| {
+        neighbourhood.neighbours_matching(seat, |point| seating_area.is_on_main_grid(point), |point| is_seat(seating_area, point))
+    });
+    let game_of_life_runner = game_of_life::GameOfLife::new(game_of_life_rules);
+    let stabelized_occupied_seats = game_of_life_runner.active_items_after_stabelizing(initially_occupied_seats.iter(), Some(seats.iter()));
+    stabelized_occupied_seats.count()
 }
 
-impl<T: grid::MutGrid<SeatState,2, CoordinateType=i128>> game_of_life::GameOfLifeRules for SimpleSeatingRules<T>{
-    type ItemType = Point<i128,2>;
-
-    fn neighbours<'a>(&self, item: &'a Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'a {
-        space::neighbour_offsets_full::<i128,2>().unwrap()
-            .into_iter()
-            .map(|offset| item.to_owned() + offset)
-            .filter(|neighbour| self.seating_area.at_point(neighbour) != &SeatState::Floor)
-            .collect()
-    }
-
-    fn flip_active(&self, active_neighbour_count: usize) -> bool {
-        active_neighbour_count >= 4
-    }
-
-    fn flip_inactive(&self, active_neighbour_count: usize) -> bool {
-        active_neighbour_count == 0
+fn is_seat(seating_area: &impl grid::Grid<SeatState, 2, CoordinateType=i128>, point: &Point<i128, 2>) -> bool{
+    match seating_area.at_point(point){
+        SeatState::Floor => false,
+        SeatState::Empty => true,
+        SeatState::Occupied => true,
     }
 }
 
-struct AdvancesSeatingRules<T>{
-    seating_area: T,
-}
-
-impl<T: grid::MutGrid<SeatState,2, CoordinateType=i128>> game_of_life::GameOfLifeRules for AdvancesSeatingRules<T>{
-    type ItemType = Point<i128,2>;
-
-    fn neighbours<'a>(&self, item: &'a Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'a {
-        space::neighbour_offsets_full::<i128,2>().unwrap()
-            .into_iter()
-            .filter_map(|offset| neighbour_in_direction(item.to_owned(),  offset, &self.seating_area))
-            .collect()
-    }
-
-    fn flip_active(&self, active_neighbour_count: usize) -> bool {
-        active_neighbour_count >= 5
-    }
-
-    fn flip_inactive(&self, active_neighbour_count: usize) -> bool {
-        active_neighbour_count == 0
-    }
-}
-
-fn neighbour_in_direction(item: Point<i128,2>, direction: Vector<i128,2>, grid: & impl grid::Grid<SeatState, 2, CoordinateType=i128>) -> Option<Point<i128,2>>{
-    if direction == Vector::new([0,0]){
-        return None;
-    }
-    let mut potential_neighbour = item + direction;
-    while grid.is_on_main_grid(&potential_neighbour){
-        match grid.at_point(&potential_neighbour){
-            SeatState::Occupied => return Some(potential_neighbour),
-            SeatState::Empty => return Some(potential_neighbour),
-            SeatState::Floor => (),
-        }
-        potential_neighbour = potential_neighbour + direction
-    }
-    None
-}
-
 
 
 
@@ -213,7 +102,6 @@ fn neighbour_in_direction(item: Point<i128,2>, direction: Vector<i128,2>, grid:
 #[cfg(test)]
 mod day11_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -235,7 +123,7 @@ L.LLLLL.LL")
         let day: Box<dyn day::DaySolver> = Box::new(Day11{});
         let problem_input = example_input();
         let expected_result = 37.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -244,25 +132,7 @@ L.LLLLL.LL")
         let day: Box<dyn day::DaySolver> = Box::new(Day11{});
         let problem_input = example_input();
         let expected_result = 26.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day11{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 11, part: 1}).unwrap();
-        let expected_result = String::from("2310");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day11{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 11, part: 2}).unwrap();
-        let expected_result = String::from("2074");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
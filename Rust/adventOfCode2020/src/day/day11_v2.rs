@@ -0,0 +1,118 @@
+use crate::grid::{self, Grid};
+use crate::space::{Neighbourhood, Point};
+use crate::game_of_life::{Automaton, AutomatonRules, StateHistogram};
+use crate::error::PuzzleError;
+use super::day11::{seat_codec, SeatState};
+
+
+pub struct Day11 {}
+
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 11,
+    name: "Seating System",
+    variant: "v2",
+    is_default: false,
+    create: || Box::new(Day11 {}),
+};
+
+impl super::Day for Day11{
+    type PuzzleInput = grid::DenseGrid<i128, SeatState, 2>;
+
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let seating_area = seat_codec().parse(&text, SeatState::default(), grid::OutsideBehaviour::Default)?;
+        Ok(seating_area)
+    }
+
+    fn solve_part1(&self, seating_area: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        let seating_rules = SeatingRules {seating_area, neighbourhood: Neighbourhood::moore(), occupied_neighbours_to_leave: 4};
+        let number_of_occupied_seats = stabelized_occupied_seat_count(seating_rules);
+        Ok(number_of_occupied_seats.to_string())
+    }
+
+    fn solve_part2(&self, seating_area: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        let seating_rules = SeatingRules {seating_area, neighbourhood: Neighbourhood::moore().line_of_sight(), occupied_neighbours_to_leave: 5};
+        let number_of_occupied_seats = stabelized_occupied_seat_count(seating_rules);
+        Ok(number_of_occupied_seats.to_string())
+    }
+}
+
+fn stabelized_occupied_seat_count(seating_rules: SeatingRules) -> usize{
+    let initial_states: Vec<(Point<i128, 2>, SeatState)> = seating_rules.seating_area.cells()
+        .map(|(point, seat_state)| (point, *seat_state))
+        .collect();
+    let automaton = Automaton::new(seating_rules);
+    let stabelized_states = automaton.states_after_stabelizing(initial_states.into_iter());
+    stabelized_states.values()
+        .filter(|seat_state| **seat_state == SeatState::Occupied)
+        .count()
+}
+
+///
+/// Unlike the rules of the first variant, the seats keep their states on the floor plan instead of
+/// being reduced to occupied and free. The floor is the default state and never changes.
+///
+struct SeatingRules{
+    seating_area: grid::DenseGrid<i128, SeatState, 2>,
+    neighbourhood: Neighbourhood<i128, 2>,
+    occupied_neighbours_to_leave: usize,
+}
+
+impl AutomatonRules for SeatingRules{
+    type ItemType = Point<i128, 2>;
+    type StateType = SeatState;
+
+    fn neighbours<'a>(&self, item: &'a Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'a {
+        self.neighbourhood.neighbours_matching(item, |point| self.seating_area.is_on_main_grid(point), |point| self.seating_area.at_point(point) != &SeatState::Floor)
+    }
+
+    fn next_state(&self, state: &SeatState, neighbour_states: &StateHistogram<SeatState>) -> SeatState {
+        let occupied_neighbour_count = neighbour_states.count(&SeatState::Occupied);
+        match state{
+            SeatState::Empty if occupied_neighbour_count == 0 => SeatState::Occupied,
+            SeatState::Occupied if occupied_neighbour_count >= self.occupied_neighbours_to_leave => SeatState::Empty,
+            _ => *state,
+        }
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod day11_v2_tests {
+    use super::*;
+    use crate::day;
+
+    fn example_input() -> String{
+        String::from(
+"L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL")
+    }
+
+    #[test]
+    fn example_part1() {
+        let day: Box<dyn day::DaySolver> = Box::new(Day11{});
+        let problem_input = example_input();
+        let expected_result = 37.to_string();
+        let actual_result = day.solve_part1(problem_input).unwrap();
+        assert_eq!(actual_result, expected_result);
+    }
+
+    #[test]
+    fn example_part2() {
+        let day: Box<dyn day::DaySolver> = Box::new(Day11{});
+        let problem_input = example_input();
+        let expected_result = 26.to_string();
+        let actual_result = day.solve_part2(problem_input).unwrap();
+        assert_eq!(actual_result, expected_result);
+    }
+}
@@ -1,7 +1,8 @@
 
 
-use crate::space::{self, Point, Vector};
-use crate::util;
+use crate::space::{self, AffineTransform, Matrix, Point, Vector};
+use crate::error::{ParseError, PuzzleError};
+use super::{parse_lines, parse_number};
 
 #[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
 pub enum Instruction{
@@ -17,146 +18,142 @@ pub enum Instruction{
 
 pub struct Day12 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 12,
+    name: "Rain Risk",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day12 {}),
+};
+
 impl super::Day for Day12{
     type PuzzleInput = Vec<Instruction>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .filter_map(parsed_instruction)
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let instructions = parse_lines(&text, parsed_instruction)?;
+        Ok(instructions)
     }
 
-    fn solve_part1(&self, instructions: Self::PuzzleInput) -> std::string::String {
-        let initial_position = Point::new([0, 0]);
-        let initial_direction = Direction::East;
-        let mut ship_state = DirectionalShipState {position: initial_position, direction: initial_direction};
-        for instruction in instructions.into_iter(){
-            ship_state.move_ship(instruction);
-        }
-        let distance = space::manhattan_metric(initial_position, ship_state.position);
-        distance.to_string()
+    fn solve_part1(&self, instructions: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        let initial_state = ShipState::new(Point::new([0, 0]), Vector::new([1, 0]));
+        let route = ShipState::route(&instructions, ShipState::move_position);
+        let final_state = initial_state.after(&route);
+        let distance = space::manhattan_metric(initial_state.position(), final_state.position());
+        Ok(distance.to_string())
     }
 
-    fn solve_part2(&self, instructions: Self::PuzzleInput) -> std::string::String {
-        let initial_position = Point::new([0, 0]);
-        let initial_waypoint = Vector::new([10,1]);
-        let mut ship_state = WaypointShipState {position: initial_position, waypoint: initial_waypoint};
-        for instruction in instructions.into_iter(){
-            ship_state.move_ship(instruction);
-        }
-        let distance = space::manhattan_metric(initial_position, ship_state.position);
-        distance.to_string()
+    fn solve_part2(&self, instructions: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        let initial_state = ShipState::new(Point::new([0, 0]), Vector::new([10, 1]));
+        let route = ShipState::route(&instructions, ShipState::move_heading);
+        let final_state = initial_state.after(&route);
+        let distance = space::manhattan_metric(initial_state.position(), final_state.position());
+        Ok(distance.to_string())
     }
 }
 
-fn parsed_instruction(instruction_text: &str) -> Option<Instruction>{
+fn parsed_instruction(instruction_text: &str) -> Result<Instruction, ParseError>{
     if instruction_text.len() < 2 || !instruction_text.is_char_boundary(1){
-        return None;
+        return Err(ParseError::in_input("Expected an instruction of the form '<action><value>'."));
     }
     let (instruction_type_text, amplitude_text) = instruction_text.split_at(1);
-    let amplitude = amplitude_text.parse::<i64>().ok()?;
+    let amplitude = parse_number::<i64>(amplitude_text, 1)?;
     match instruction_type_text{
-        "E" => Some(Instruction::East(amplitude)),
-        "N" => Some(Instruction::North(amplitude)),
-        "W" => Some(Instruction::West(amplitude)),
-        "S" => Some(Instruction::South(amplitude)),
-        "L" => Some(Instruction::Left(amplitude)),
-        "R" => Some(Instruction::Right(amplitude)),
-        "F" => Some(Instruction::Forward(amplitude)),
+        "E" => Ok(Instruction::East(amplitude)),
+        "N" => Ok(Instruction::North(amplitude)),
+        "W" => Ok(Instruction::West(amplitude)),
+        "S" => Ok(Instruction::South(amplitude)),
+        "L" => Ok(Instruction::Left(amplitude)),
+        "R" => Ok(Instruction::Right(amplitude)),
+        "F" => Ok(Instruction::Forward(amplitude)),
+        _ => Err(ParseError::in_line(0, &format!("Unknown action '{}'.", instruction_type_text))),
+    }
+}
+
+fn compass_direction(instruction: Instruction) -> Option<(Vector<i64, 2>, i64)>{
+    match instruction{
+        Instruction::East(amp) => Some((Vector::new([1, 0]), amp)),
+        Instruction::North(amp) => Some((Vector::new([0, 1]), amp)),
+        Instruction::West(amp) => Some((Vector::new([-1, 0]), amp)),
+        Instruction::South(amp) => Some((Vector::new([0, -1]), amp)),
         _ => None,
     }
 }
 
+///
+/// Position of the ship together with its heading, which is either the direction it faces or the waypoint.
+/// The state is stored as the point `[x, y, heading_x, heading_y]`, so that every instruction is an affine transform of it.
+///
 #[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
-enum Direction{
-    North,
-    West,
-    South,
-    East,
+struct ShipState{
+    state: Point<i64, 4>,
 }
 
-impl Direction {
-    fn rotate(self, degrees: i64) -> Direction{
-        let degrees_from_north = i64::from(self) + degrees;
-        Direction::from(degrees_from_north)
+impl ShipState{
+    fn new(position: Point<i64, 2>, heading: Vector<i64, 2>) -> ShipState{
+        ShipState {state: Point::new([position[0], position[1], heading[0], heading[1]])}
     }
-}
 
-impl From<Direction> for i64{
-    fn from(direction: Direction) -> Self {
-        match direction{
-            Direction::North => 0,
-            Direction::West => 90,
-            Direction::South => 180,
-            Direction::East => 270,
-        }
+    fn position(&self) -> Point<i64, 2>{
+        Point::new([self.state[0], self.state[1]])
     }
-}
 
-impl From<i64> for Direction{
-    fn from(degrees: i64) -> Self {
-        let effective_degrees = util::modulo(degrees, 360);
-        let rotation_steps = (effective_degrees + 45) / 90;
-        match rotation_steps{
-            0 => Direction::North,
-            1 => Direction::West,
-            2 => Direction::South,
-            3 => Direction::East,
-            _ => unreachable!("This cannot happen because of how modulo works."),
-        }
+    fn after(&self, transform: &AffineTransform<i64, 4>) -> ShipState{
+        ShipState {state: transform.apply(self.state)}
     }
-}
 
-impl From<Direction> for Vector<i64, 2>{
-    fn from(direction: Direction) -> Self {
-        match direction{
-            Direction::North => Vector::new([0, 1]),
-            Direction::West => Vector::new([-1, 0]),
-            Direction::South => Vector::new([0, -1]),
-            Direction::East => Vector::new([1, 0]),
-        }
+    ///
+    /// Composes the transforms of all instructions into a single transform.
+    ///
+    fn route(instructions: &[Instruction], compass_move: fn(Vector<i64, 2>) -> AffineTransform<i64, 4>) -> AffineTransform<i64, 4>{
+        instructions.iter()
+            .map(|instruction| ShipState::instruction_transform(*instruction, compass_move))
+            .fold(AffineTransform::identity(), |route, transform| route.then(&transform))
     }
-}
 
-#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
-struct DirectionalShipState{
-    position: Point<i64, 2>,
-    direction: Direction,
-}
-
-impl DirectionalShipState{
-    fn move_ship(&mut self, instruction: Instruction){
+    fn instruction_transform(instruction: Instruction, compass_move: fn(Vector<i64, 2>) -> AffineTransform<i64, 4>) -> AffineTransform<i64, 4>{
+        if let Some((direction, amp)) = compass_direction(instruction){
+            return compass_move(direction * amp);
+        }
         match instruction{
-            Instruction::East(amp) => self.position = self.position + Vector::from(Direction::East) * amp,
-            Instruction::North(amp) => self.position = self.position + Vector::from(Direction::North) * amp,
-            Instruction::West(amp) => self.position = self.position + Vector::from(Direction::West) * amp,
-            Instruction::South(amp) => self.position = self.position + Vector::from(Direction::South) * amp,
-            Instruction::Left(amp) => self.direction = self.direction.rotate(amp),
-            Instruction::Right(amp) => self.direction = self.direction.rotate(-amp),
-            Instruction::Forward(amp) => self.position = self.position + Vector::from(self.direction) * amp,
+            Instruction::Left(amp) => ShipState::turn(amp),
+            Instruction::Right(amp) => ShipState::turn(-amp),
+            Instruction::Forward(amp) => ShipState::forward(amp),
+            _ => unreachable!("Compass directions are handled above."),
         }
     }
-}
 
+    fn move_position(offset: Vector<i64, 2>) -> AffineTransform<i64, 4>{
+        AffineTransform::translation(Vector::new([offset[0], offset[1], 0, 0]))
+    }
 
+    fn move_heading(offset: Vector<i64, 2>) -> AffineTransform<i64, 4>{
+        AffineTransform::translation(Vector::new([0, 0, offset[0], offset[1]]))
+    }
 
-#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
-struct WaypointShipState{
-    position: Point<i64, 2>,
-    waypoint: Vector<i64, 2>,
-}
+    ///
+    /// Rotates the heading counter-clockwise.
+    ///
+    fn turn(angle: i64) -> AffineTransform<i64, 4>{
+        let rotation: Matrix<i64, 2, 2> = Matrix::quarter_turn_rotation(angle);
+        AffineTransform::linear(Matrix::new([
+            [1, 0, 0, 0],
+            [0, 1, 0, 0],
+            [0, 0, rotation[(0, 0)], rotation[(0, 1)]],
+            [0, 0, rotation[(1, 0)], rotation[(1, 1)]],
+        ]))
+    }
 
-impl WaypointShipState{
-    fn move_ship(&mut self, instruction: Instruction){
-        match instruction{
-            Instruction::East(amp) => self.waypoint = self.waypoint + Vector::from(Direction::East) * amp,
-            Instruction::North(amp) => self.waypoint = self.waypoint + Vector::from(Direction::North) * amp,
-            Instruction::West(amp) => self.waypoint = self.waypoint + Vector::from(Direction::West) * amp,
-            Instruction::South(amp) => self.waypoint = self.waypoint + Vector::from(Direction::South) * amp,
-            Instruction::Left(amp) => self.waypoint = self.waypoint.rotate(amp),
-            Instruction::Right(amp) => self.waypoint = self.waypoint.rotate(-amp),
-            Instruction::Forward(amp) => self.position = self.position + self.waypoint * amp,
-        }
+    ///
+    /// Moves the position by the heading the given number of times.
+    ///
+    fn forward(times: i64) -> AffineTransform<i64, 4>{
+        AffineTransform::linear(Matrix::new([
+            [1, 0, times, 0],
+            [0, 1, 0, times],
+            [0, 0, 1, 0],
+            [0, 0, 0, 1],
+        ]))
     }
 }
 
@@ -164,7 +161,6 @@ impl WaypointShipState{
 #[cfg(test)]
 mod day12_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -181,7 +177,7 @@ F11")
         let day: Box<dyn day::DaySolver> = Box::new(Day12{});
         let problem_input = example_input();
         let expected_result = 25.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -190,25 +186,7 @@ F11")
         let day: Box<dyn day::DaySolver> = Box::new(Day12{});
         let problem_input = example_input();
         let expected_result = 286.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day12{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 12, part: 1}).unwrap();
-        let expected_result = String::from("1441");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day12{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 12, part: 2}).unwrap();
-        let expected_result = String::from("61616");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
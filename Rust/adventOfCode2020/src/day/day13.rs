@@ -1,53 +1,63 @@
 use std::convert::TryFrom;
 
 use crate::algebra;
+use crate::error::{ParseError, PuzzleError};
+use super::parse_number;
 
 pub struct Day13 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 13,
+    name: "Shuttle Search",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day13 {}),
+};
+
 impl super::Day for Day13{
     type PuzzleInput = (i128, Vec<String>);
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
         let mut lines = text.lines();
 
-        let maybe_earliest_time = lines.next();
-        if maybe_earliest_time.is_none() {
-            return (0, vec![]);
-        }
-        let possibly_earliest_time = maybe_earliest_time.unwrap().parse::<i128>();
-        if possibly_earliest_time.is_err(){
-            return (0, vec![]);
-        }
-        let earliest_time = possibly_earliest_time.unwrap();
-
-        let maybe_timetable = lines.next();
-        if maybe_timetable.is_none(){
-            return (earliest_time, vec![]);
+        let earliest_time_text = lines.next()
+            .ok_or_else(|| ParseError::in_input("The earliest departure time is missing."))?;
+        let earliest_time = parse_number::<i128>(earliest_time_text, 0)
+            .map_err(|error| error.on_line(0))?;
+
+        let timetable_text = lines.next()
+            .ok_or_else(|| ParseError::in_input("The timetable is missing."))?;
+        let mut timetable: Vec<String> = vec![];
+        let mut column_index = 0;
+        for item in timetable_text.split(','){
+            if item != "x"{
+                parse_number::<i128>(item, column_index)
+                    .map_err(|error| error.on_line(1))?;
+            }
+            timetable.push(item.to_owned());
+            column_index += item.len() + 1;
         }
-        let timetable: Vec<String> = maybe_timetable.unwrap()
-            .split(',')
-            .map(|item| item.to_owned())
-            .collect();
-        (earliest_time, timetable)
+        Ok((earliest_time, timetable))
     }
 
-    fn solve_part1(&self, (earliest_time, timetable): Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, (earliest_time, timetable): Self::PuzzleInput) -> Result<String, PuzzleError> {
         if timetable.is_empty(){
-            return String::from("No timetable!");
+            return Err(PuzzleError::unsolvable("No timetable!"));
         }
         let bus_ids = timetable.iter()
             .filter_map(|id| id.parse::<i128>().ok());
         let ids_with_waiting_time = bus_ids.map(|id| (id, bus_waiting_time(earliest_time, id)));
         let best_connection = ids_with_waiting_time.min_by(|(_id1, time1),(_id2, time2)| time1.cmp(time2));
         if best_connection.is_none(){
-            return String::from("No connections!");
+            return Err(PuzzleError::unsolvable("No connections!"));
         }
         let (shortest_wait, corresponding_bus) = best_connection.unwrap();
         let result = shortest_wait * corresponding_bus;
-        result.to_string()
+        Ok(result.to_string())
     }
 
-    fn solve_part2(&self, (_earliest_time, timetable): Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, (_earliest_time, timetable): Self::PuzzleInput) -> Result<String, PuzzleError> {
         let contest_data: Vec<(i128, i128)> = timetable.into_iter()
             .enumerate()
             .filter_map(|(index, id_text)| match id_text.parse::<i128>(){
@@ -61,8 +71,8 @@ impl super::Day for Day13{
             .collect();
         let result = algebra::chinese_remainder(contest_data);
         match result{
-            None => String::from("There is no result!"),
-            Some(solution) => solution.to_string()
+            None => Err(PuzzleError::unsolvable("There is no result!")),
+            Some(solution) => Ok(solution.to_string())
         }
     }
 }
@@ -82,7 +92,6 @@ fn time_since_last_departure(offset_from_first_bus: i128, bus_id: i128) -> i128{
 #[cfg(test)]
 mod day13_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -96,7 +105,7 @@ mod day13_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day13{});
         let problem_input = example_input();
         let expected_result = 295.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -105,25 +114,7 @@ mod day13_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day13{});
         let problem_input = example_input();
         let expected_result = 1068781.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day13{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 13, part: 1}).unwrap();
-        let expected_result = String::from("161");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day13{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 13, part: 2}).unwrap();
-        let expected_result = String::from("213890632230818");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
-
 use std::collections::HashMap;
 
+use crate::error::{ParseError, PuzzleError};
+use super::{parse_lines, parse_number};
+
 #[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
 pub struct BitMask{
     one_mask: u64,
@@ -22,63 +24,82 @@ pub enum Instruction{
 
 pub struct Day14 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 14,
+    name: "Docking Data",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day14 {}),
+};
+
 impl super::Day for Day14{
     type PuzzleInput = Vec<Instruction>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .filter_map(parsed_instruction)
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let instructions = parse_lines(&text, parsed_instruction)?;
+        Ok(instructions)
     }
 
-    fn solve_part1(&self, instructions: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, instructions: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let initial_bit_mask = BitMask {one_mask: 0, x_mask: 0};
         let memory_manager = DockingComputerMemoryManagerMk1 {};
         let mut computer = DockingComputer::new(memory_manager, initial_bit_mask);
         computer.execute(instructions);
         let result = computer.memory_sum();
-        result.to_string()
+        Ok(result.to_string())
     }
 
-    fn solve_part2(&self, instructions: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, instructions: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let initial_bit_mask = BitMask {one_mask: 0, x_mask: 0};
         let memory_manager = DockingComputerMemoryManagerMk2 {};
         let mut computer = DockingComputer::new(memory_manager, initial_bit_mask);
         computer.execute(instructions);
         let result = computer.memory_sum();
-        result.to_string()
+        Ok(result.to_string())
     }
 }
 
-fn parsed_instruction(instruction_text: &str) -> Option<Instruction>{
+fn parsed_instruction(instruction_text: &str) -> Result<Instruction, ParseError>{
     if instruction_text.starts_with("mask"){
         let bit_mask = parsed_bitmask(instruction_text)?;
-        return Some(Instruction::SetBitMask(bit_mask));
+        return Ok(Instruction::SetBitMask(bit_mask));
     }
     let memory_operation = parsed_memory_operation(instruction_text)?;
-    Some(Instruction::SetMemory(memory_operation))
+    Ok(Instruction::SetMemory(memory_operation))
 }
 
-fn parsed_bitmask(bit_mask_line: &str) -> Option<BitMask>{
-    let mask_text = bit_mask_line.strip_prefix("mask = ")?;
-    let one_mask = u64::from_str_radix(&mask_text.replace("X", "0"),2).ok()?;
-    let x_mask = u64::from_str_radix(
-        &mask_text
-            .replace("1", "0")
-            .replace("X", "1"),
-        2)
-        .ok()?;
-    Some(BitMask {one_mask, x_mask})
+fn parsed_bitmask(bit_mask_line: &str) -> Result<BitMask, ParseError>{
+    let prefix = "mask = ";
+    let mask_text = bit_mask_line.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::in_input("Expected a bit mask of the form 'mask = <bits>'."))?;
+    if mask_text.len() > 64{
+        return Err(ParseError::in_line(prefix.len(), "A bit mask can have at most 64 bits."));
+    }
+    let mut one_mask = 0;
+    let mut x_mask = 0;
+    for (index, c) in mask_text.chars().enumerate(){
+        one_mask <<= 1;
+        x_mask <<= 1;
+        match c{
+            '1' => one_mask += 1,
+            'X' => x_mask += 1,
+            '0' => (),
+            _ => return Err(ParseError::in_line(prefix.len() + index, &format!("Unexpected character '{}' in bit mask.", c))),
+        }
+    }
+    Ok(BitMask {one_mask, x_mask})
 }
 
-fn parsed_memory_operation(operation_text: &str) -> Option<MemoryOperation>{
+fn parsed_memory_operation(operation_text: &str) -> Result<MemoryOperation, ParseError>{
     lazy_static! {
         static ref OPERATION_RE: regex::Regex = regex::Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
     }
-    let captures: regex::Captures = OPERATION_RE.captures(operation_text)?;
-    let target = captures[1].parse::<u64>().ok()?;
-    let value = captures[2].parse::<u64>().ok()?;
-    Some(MemoryOperation {target, value})
+    let captures: regex::Captures = OPERATION_RE.captures(operation_text)
+        .ok_or_else(|| ParseError::in_input("Expected a memory operation of the form 'mem[<address>] = <value>'."))?;
+    let target = parse_number::<u64>(&captures[1], captures.get(1).unwrap().start())?;
+    let value = parse_number::<u64>(&captures[2], captures.get(2).unwrap().start())?;
+    Ok(MemoryOperation {target, value})
 }
 
 trait DockingComputerMemoryManager{
@@ -180,7 +201,6 @@ fn bit_combinations(bits: impl Iterator<Item=u64>) -> Vec<u64>{
 #[cfg(test)]
 mod day14_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -204,7 +224,7 @@ mem[26] = 1")
         let day: Box<dyn day::DaySolver> = Box::new(Day14{});
         let problem_input = example_input();
         let expected_result = 165.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -213,25 +233,7 @@ mem[26] = 1")
         let day: Box<dyn day::DaySolver> = Box::new(Day14{});
         let problem_input = example_input2();
         let expected_result = 208.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day14{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 14, part: 1}).unwrap();
-        let expected_result = String::from("12408060320841");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day14{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 14, part: 2}).unwrap();
-        let expected_result = String::from("4466434626828");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
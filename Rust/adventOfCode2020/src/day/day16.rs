@@ -1,5 +1,8 @@
 use std::collections::HashSet;
 
+use crate::error::{ParseError, PuzzleError};
+use super::{parse_lines, parse_number};
+
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
 pub struct TicketRule{
     name: String,
@@ -21,33 +24,44 @@ pub struct TicketData{
 
 pub struct Day16 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 16,
+    name: "Ticket Translation",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day16 {}),
+};
+
 impl super::Day for Day16{
-    type PuzzleInput = Option<TicketData>;
+    type PuzzleInput = TicketData;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
         let sections: Vec<&str> = text.split("\n\n").collect();
         if sections.len() < 3{
-            return None;
+            return Err(ParseError::in_input("Expected sections for the rules, your ticket and nearby tickets.").into());
         }
-        let rules = parsed_ticket_rules(sections[0]);
+        let own_ticket_line_offset = sections[0].lines().count() + 1;
+        let other_tickets_line_offset = own_ticket_line_offset + sections[1].lines().count() + 1;
 
-        let my_ticket_text = sections[1].lines().nth(1)?;
-        let own_ticket = parsed_ticket(my_ticket_text);
+        let rules = parse_lines(sections[0], parsed_ticket_rule)?;
+
+        let my_ticket_text = sections[1].lines().nth(1)
+            .ok_or_else(|| ParseError::at(own_ticket_line_offset, 0, "Your ticket is missing."))?;
+        let own_ticket = parsed_ticket(my_ticket_text)
+            .map_err(|error| error.on_line(own_ticket_line_offset + 1))?;
 
         let other_tickets = sections[2]
             .lines()
+            .enumerate()
             .skip(1)
-            .map(parsed_ticket)
-            .collect();
+            .map(|(line_index, line)| parsed_ticket(line).map_err(|error| error.on_line(other_tickets_line_offset + line_index)))
+            .collect::<Result<_, _>>()?;
 
-        Some(TicketData {rules, own_ticket, other_tickets})
+        Ok(TicketData {rules, own_ticket, other_tickets})
     }
 
-    fn solve_part1(&self, maybe_ticket_data: Self::PuzzleInput) -> std::string::String {
-        if maybe_ticket_data.is_none(){
-            return String::from("Invalid input!");
-        }
-        let ticket_data = maybe_ticket_data.unwrap();
+    fn solve_part1(&self, ticket_data: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let rules = ticket_data.rules;
         let invalid_entries = ticket_data.other_tickets.iter()
             .map(|ticket| ticket.entries
@@ -55,15 +69,10 @@ impl super::Day for Day16{
                 .filter(|entry| !is_valid_by_some_rule(entry, &rules))
             ).flatten();
         let error_rate: u32 = invalid_entries.sum();
-        error_rate.to_string()
+        Ok(error_rate.to_string())
     }
 
-    fn solve_part2(&self, maybe_ticket_data: Self::PuzzleInput) -> std::string::String {
-        if maybe_ticket_data.is_none(){
-            return String::from("Invalid input!");
-        }
-        let ticket_data = maybe_ticket_data.unwrap();
-
+    fn solve_part2(&self, ticket_data: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let rules = ticket_data.rules;
         let own_ticket = ticket_data.own_ticket;
         let mut rules_for_entries:Vec<HashSet<TicketRule>> = own_ticket
@@ -86,7 +95,7 @@ impl super::Day for Day16{
 
         let maybe_assigned_rules = unique_rules_for_entries(&rules_for_entries);
         if maybe_assigned_rules.is_none(){
-            return String::from("No unique solution could be found!");
+            return Err(PuzzleError::unsolvable("No unique solution could be found!"));
         }
         let assigned_rules = maybe_assigned_rules.unwrap();
 
@@ -97,34 +106,33 @@ impl super::Day for Day16{
             } else {
                 1
             }).product();
-        result.to_string()
+        Ok(result.to_string())
     }
 }
 
-fn parsed_ticket_rules(rules_text: &str) -> Vec<TicketRule>{
-    rules_text.lines()
-        .filter_map(parsed_ticket_rule)
-        .collect()
-}
-
-fn parsed_ticket_rule(rule_text: &str) -> Option<TicketRule>{
+fn parsed_ticket_rule(rule_text: &str) -> Result<TicketRule, ParseError>{
     lazy_static! {
         static ref RULE_RE: regex::Regex = regex::Regex::new(r"^((\w|\s)+): (\d+)-(\d+) or (\d+)-(\d+)$").unwrap();
     }
-    let captures: regex::Captures = RULE_RE.captures(rule_text)?;
+    let captures: regex::Captures = RULE_RE.captures(rule_text)
+        .ok_or_else(|| ParseError::in_input("Expected a rule of the form '<name>: <a>-<b> or <c>-<d>'."))?;
     let name = captures[1].to_owned();
-    let low_lower_bound = captures[3].parse::<u32>().ok()?;
-    let low_upper_bound = captures[4].parse::<u32>().ok()? + 1; //Rust upper bounds are usually not inclusive.
-    let high_lower_bound = captures[5].parse::<u32>().ok()?;
-    let high_upper_bound = captures[6].parse::<u32>().ok()? + 1;
-    Some(TicketRule {name, low_range: (low_lower_bound, low_upper_bound), high_range: (high_lower_bound, high_upper_bound)})
+    let bound = |group: usize| parse_number::<u32>(&captures[group], captures.get(group).unwrap().start());
+    let low_lower_bound = bound(3)?;
+    let low_upper_bound = bound(4)? + 1; //Rust upper bounds are usually not inclusive.
+    let high_lower_bound = bound(5)?;
+    let high_upper_bound = bound(6)? + 1;
+    Ok(TicketRule {name, low_range: (low_lower_bound, low_upper_bound), high_range: (high_lower_bound, high_upper_bound)})
 }
 
-fn parsed_ticket(ticket_text: &str) -> Ticket{
-    let ticket_entries = ticket_text.split(',')
-        .filter_map(|item| item.parse::<u32>().ok())
-        .collect();
-    Ticket {entries: ticket_entries}
+fn parsed_ticket(ticket_text: &str) -> Result<Ticket, ParseError>{
+    let mut ticket_entries = vec![];
+    let mut column_index = 0;
+    for item in ticket_text.split(','){
+        ticket_entries.push(parse_number::<u32>(item, column_index)?);
+        column_index += item.len() + 1;
+    }
+    Ok(Ticket {entries: ticket_entries})
 }
 
 
@@ -194,7 +202,6 @@ fn unique_rules_for_entries(rules_by_entry: &[HashSet<TicketRule>]) -> Option<Ve
 #[cfg(test)]
 mod day16_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -218,25 +225,7 @@ nearby tickets:
         let day: Box<dyn day::DaySolver> = Box::new(Day16{});
         let problem_input = example_input();
         let expected_result = 71.to_string();
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day16{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 16, part: 1}).unwrap();
-        let expected_result = String::from("26941");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day16{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 16, part: 2}).unwrap();
-        let expected_result = String::from("634796407951");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
@@ -1,78 +1,65 @@
-use std::convert::TryFrom;
+use crate::game_of_life::{Backend, LifeLikeRule, LifeLikeRules};
+use crate::grid::TextCodec;
+use crate::space::{Neighbourhood, Point};
+use crate::error::PuzzleError;
 
-use crate::game_of_life;
-use crate::space::{self, Point};
 
+///
+/// The variants only differ in the backend playing the game of life.
+///
+pub struct Day17 {
+    pub(super) backend: Backend,
+}
 
-pub struct Day17 {}
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 17,
+    name: "Conway Cubes",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day17 {backend: Backend::Hashed}),
+};
 
 impl super::Day for Day17{
     type PuzzleInput = Vec<Point<i64,2>>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .enumerate()
-            .filter_map(|(y, line)| match i64::try_from(y) {
-                Ok(y_value) => Some((y_value, line)),
-                Err(_) => None,
-            })
-            .map(|(y, line)| line.chars()
-                .enumerate()
-                .filter_map(|(x, line)| match i64::try_from(x) {
-                    Ok(x_value) => Some((x_value, line)),
-                    Err(_) => None,
-                })
-                .filter_map(move |(x, c)| match c{
-                    '#' => Some(Point::new([x,y])),
-                    _ => None,
-                }))
-            .flatten()
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let codec = TextCodec::new(vec![('#', true), ('.', false)]);
+        let active_points = codec.parse_values(&text)?
+            .into_iter()
+            .filter(|(_, is_active)| *is_active)
+            .map(|(point, _)| point)
+            .collect();
+        Ok(active_points)
     }
 
-    fn solve_part1(&self, active_plane_points: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, active_plane_points: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let initially_active_points: Vec<Point<i64,3>> = active_plane_points.into_iter()
             .map(|point| Point::new([point[0], point[1], 0]))
             .collect();
-        let conway_cube = ConwayCube::<3> {};
-        let game_of_life_runner = game_of_life::GameOfLife::new(conway_cube);
-        let active_points = game_of_life_runner.active_items_after_playing(6, initially_active_points.iter());
-        let result = active_points.count();
-        result.to_string()
+        let conway_cube = LifeLikeRules::with_neighbourhood(LifeLikeRule::parse(CONWAY_CUBE_RULE)?, Neighbourhood::<i64, 3>::moore());
+        let active_points = self.backend.active_points_after_playing(conway_cube, 6, initially_active_points.iter())
+            .ok_or_else(|| PuzzleError::unsolvable("The backend cannot play the rule of the Conway cubes."))?;
+        let result = active_points.len();
+        Ok(result.to_string())
     }
 
-    fn solve_part2(&self, active_plane_points: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, active_plane_points: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let initially_active_points: Vec<Point<i64,4>> = active_plane_points.into_iter()
             .map(|point| Point::new([point[0], point[1], 0, 0]))
             .collect();
-        let conway_cube = ConwayCube::<4> {};
-        let game_of_life_runner = game_of_life::GameOfLife::new(conway_cube);
-        let active_points = game_of_life_runner.active_items_after_playing(6, initially_active_points.iter());
-        let result = active_points.count();
-        result.to_string()
+        let conway_cube = LifeLikeRules::with_neighbourhood(LifeLikeRule::parse(CONWAY_CUBE_RULE)?, Neighbourhood::<i64, 4>::moore());
+        let active_points = self.backend.active_points_after_playing(conway_cube, 6, initially_active_points.iter())
+            .ok_or_else(|| PuzzleError::unsolvable("The backend cannot play the rule of the Conway cubes."))?;
+        let result = active_points.len();
+        Ok(result.to_string())
     }
 }
 
-struct ConwayCube<const N: usize> {}
-
-impl<const N: usize> game_of_life::GameOfLifeRules for ConwayCube<N>{
-    type ItemType = Point<i64, N>;
-
-    fn neighbours<'a>(&self, item: &'a Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'a {
-        let offsets = space::neighbour_offsets_full().unwrap();
-        offsets.into_iter()
-            .map(|offset| item.to_owned() + offset)
-            .collect()
-    }
-
-    fn flip_active(&self, active_neighbour_count: usize) -> bool {
-        active_neighbour_count != 2 && active_neighbour_count != 3
-    }
-
-    fn flip_inactive(&self, active_neighbour_count: usize) -> bool {
-        active_neighbour_count == 3
-    }
-}
+///
+/// Inactive cubes become active with exactly 3 active neighbours and active cubes stay active with 2 or 3.
+///
+const CONWAY_CUBE_RULE: &str = "B3/S23";
 
 
 
@@ -80,8 +67,10 @@ impl<const N: usize> game_of_life::GameOfLifeRules for ConwayCube<N>{
 #[cfg(test)]
 mod day17_tests {
     use super::*;
-    use crate::input;
-    use crate::day;
+    use crate::day::{self, Day};
+    use crate::game_of_life::GameOfLife;
+    use crate::grid::InfiniteGrid;
+    use rstest::rstest;
 
     fn example_input() -> String{
         String::from(
@@ -90,39 +79,39 @@ mod day17_tests {
 ###")
     }
 
-    #[test]
-    fn example_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day17{});
+    #[rstest]
+    #[case(Backend::Hashed)]
+    #[case(Backend::Dense)]
+    fn example_part1(#[case] backend: Backend) {
+        let day: Box<dyn day::DaySolver> = Box::new(Day17{backend});
         let problem_input = example_input();
         let expected_result = 112.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
     #[test]
-    fn example_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day17{});
-        let problem_input = example_input();
-        let expected_result = 848.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day17{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 17, part: 1}).unwrap();
-        let expected_result = String::from("232");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
+    fn first_cycle_of_the_example() {
+        let initially_active_points: Vec<Point<i64, 3>> = Day17{backend: Backend::Hashed}.parse_input(example_input()).unwrap()
+            .into_iter()
+            .map(|point| Point::new([point[0], point[1], 0]))
+            .collect();
+        let game_of_life_runner = GameOfLife::new(LifeLikeRules::with_neighbourhood(LifeLikeRule::parse(CONWAY_CUBE_RULE).unwrap(), Neighbourhood::<i64, 3>::moore()));
+        let active_points = game_of_life_runner.active_items_after_playing(1, initially_active_points.iter());
+        let grid = InfiniteGrid::new(false, active_points.map(|point| (point, true)));
+        let codec = TextCodec::new(vec![('#', true), ('.', false)]);
+        let expected_text = "z=-1\n#..\n..#\n.#.\n\nz=0\n#.#\n.##\n.#.\n\nz=1\n#..\n..#\n.#.";
+        assert_eq!(codec.render(&grid), expected_text);
     }
 
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day17{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 17, part: 2}).unwrap();
-        let expected_result = String::from("1620");
-        let actual_result = day.solve_part2(problem_input);
+    #[rstest]
+    #[case(Backend::Hashed)]
+    #[case(Backend::Dense)]
+    fn example_part2(#[case] backend: Backend) {
+        let day: Box<dyn day::DaySolver> = Box::new(Day17{backend});
+        let problem_input = example_input();
+        let expected_result = 848.to_string();
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
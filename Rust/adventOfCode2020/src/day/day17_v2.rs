@@ -0,0 +1,12 @@
+use crate::game_of_life::Backend;
+use super::day17::Day17;
+
+
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 17,
+    name: "Conway Cubes",
+    variant: "v2",
+    is_default: false,
+    create: || Box::new(Day17 {backend: Backend::Dense}),
+};
@@ -1,38 +1,56 @@
 use crate::formula_parsing::{Formula};
 use crate::formula_parsing::formula_parser_v2::{self, FormulaParser};
+use crate::error::{ParseError, PuzzleError};
 
 pub struct Day18 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 18,
+    name: "Operation Order",
+    variant: "v2",
+    is_default: true,
+    create: || Box::new(Day18 {}),
+};
+
 impl super::Day for Day18{
     type PuzzleInput = Vec<Formula>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let formulas = text.lines()
             .map(|line| Formula(line.to_owned()))
-            .collect()
+            .collect();
+        Ok(formulas)
     }
 
-    fn solve_part1(&self, formulas: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, formulas: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let parsing_strategy = formula_parser_v2::LeftToRightEvaluationBinaryOpParsingStrategy::new();
         let parser = formula_parser_v2::RightToLeftFormulaParser::new(parsing_strategy);
-        let result: i128 = formulas.iter()
-            .filter_map(|formula| parser.parse(formula))
-            .map(|expr| expr.evaluate())
-            .sum();
-        result.to_string()
+        let result = sum_of_formulas(&formulas, &parser)?;
+        Ok(result.to_string())
     }
 
-    fn solve_part2(&self, formulas: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, formulas: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let parsing_strategy = formula_parser_v2::PlusBeforeMultEvaluationBinaryOpParsingStrategy::new();
         let parser = formula_parser_v2::RightToLeftFormulaParser::new(parsing_strategy);
-        let result: i128 = formulas.iter()
-            .filter_map(|formula| parser.parse(formula))
-            .map(|expr| expr.evaluate())
-            .sum();
-        result.to_string()
+        let result = sum_of_formulas(&formulas, &parser)?;
+        Ok(result.to_string())
     }
 }
 
+fn sum_of_formulas(formulas: &[Formula], parser: &impl FormulaParser) -> Result<i128, ParseError>{
+    let mut sum = 0;
+    for (line_index, formula) in formulas.iter().enumerate(){
+        if formula.0.trim().is_empty(){
+            continue;
+        }
+        let expression = parser.parse(formula)
+            .ok_or_else(|| ParseError::in_input("Invalid formula.").on_line(line_index))?;
+        sum += expression.evaluate();
+    }
+    Ok(sum)
+}
+
 
 
 
@@ -40,7 +58,6 @@ impl super::Day for Day18{
 #[cfg(test)]
 mod day18_tests {
     use super::*;
-    use crate::input;
     use crate::day;
     use rstest::rstest;
 
@@ -54,7 +71,7 @@ mod day18_tests {
     fn examples_part1(#[case] problem_input: String, #[case] expected_result: i128) {
         let day: Box<dyn day::DaySolver> = Box::new(Day18{});
         let expected_result_text = expected_result.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result_text);
     }
 
@@ -68,25 +85,7 @@ mod day18_tests {
     fn examples_part2(#[case] problem_input: String, #[case] expected_result: i128) {
         let day: Box<dyn day::DaySolver> = Box::new(Day18{});
         let expected_result_text = expected_result.to_string();
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result_text);
     }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day18{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 18, part: 1}).unwrap();
-        let expected_result = String::from("280014646144");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day18{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 18, part: 2}).unwrap();
-        let expected_result = String::from("9966990988262");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
-
 use crate::parser_generator;
+use crate::error::{ParseError, PuzzleError};
 
 
 pub struct MessageData{
@@ -9,17 +9,29 @@ pub struct MessageData{
 
 pub struct Day19 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 19,
+    name: "Monster Messages",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day19 {}),
+};
+
 impl super::Day for Day19{
     type PuzzleInput = MessageData;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
         let sections: Vec<&str> = text.split("\n\n").collect();
+        if sections.len() < 2{
+            return Err(ParseError::in_input("Expected a section with rules and a section with messages.").into());
+        }
         let grammar_text = sections[0].to_owned();
         let messages = sections[1].lines().map(|line| line.to_owned()).collect();
-        MessageData { grammar_text, messages }
+        Ok(MessageData { grammar_text, messages })
     }
 
-    fn solve_part1(&self, data: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, data: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let grammar = parser_generator::parse_grammar(&data.grammar_text, None);
         let parser = parser_generator::Parser::new(grammar);
         let possibilities_to_parse_messages = data.messages.iter()
@@ -27,10 +39,10 @@ impl super::Day for Day19{
         let number_of_parsable_messages = possibilities_to_parse_messages
             .filter(|possibilities| !possibilities.is_empty())
             .count();
-        number_of_parsable_messages.to_string()
+        Ok(number_of_parsable_messages.to_string())
     }
 
-    fn solve_part2(&self, data: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, data: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let correction_rules = "8: 42 | 42 8
 11: 42 31 | 42 11 31";
         let grammar = parser_generator::parse_grammar(&data.grammar_text, Some(correction_rules));
@@ -40,7 +52,7 @@ impl super::Day for Day19{
         let number_of_parsable_messages = possibilities_to_parse_messages
             .filter(|possibilities| !possibilities.is_empty())
             .count();
-        number_of_parsable_messages.to_string()
+        Ok(number_of_parsable_messages.to_string())
     }
 }
 
@@ -50,7 +62,6 @@ impl super::Day for Day19{
 #[cfg(test)]
 mod day19_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -125,7 +136,7 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#)
         let day: Box<dyn day::DaySolver> = Box::new(Day19{});
         let problem_input = example_input();
         let expected_result = 2.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -134,7 +145,7 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#)
         let day: Box<dyn day::DaySolver> = Box::new(Day19{});
         let problem_input = example_input2();
         let expected_result = 3.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -143,25 +154,7 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#)
         let day: Box<dyn day::DaySolver> = Box::new(Day19{});
         let problem_input = example_input2();
         let expected_result = 12.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day19{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 19, part: 1}).unwrap();
-        let expected_result = String::from("144");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day19{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 19, part: 2}).unwrap();
-        let expected_result = String::from("260");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
-
+use crate::error::{ParseError, PuzzleError};
+use super::{parse_lines, parse_number};
 
 pub struct PasswordRule{
     character: char,
@@ -8,41 +9,53 @@ pub struct PasswordRule{
 
 pub struct Day2 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 2,
+    name: "Password Philosophy",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day2 {}),
+};
+
 impl super::Day for Day2{
     type PuzzleInput = Vec<(String, PasswordRule)>;
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .flat_map(|line| parse_password_data(line))
-            .collect::<Vec<(String, PasswordRule)>>()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let password_data = parse_lines(&text, parse_password_data)?;
+        Ok(password_data)
     }
 
-    fn solve_part1(&self, input: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, input: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let number_of_valid_passwords = input.iter()
             .filter(|(password, rule)| is_valid_sled_password(password, rule))
             .count();
-        number_of_valid_passwords.to_string()
+        Ok(number_of_valid_passwords.to_string())
     }
 
-    fn solve_part2(&self, input: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, input: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let number_of_valid_passwords = input.iter()
             .filter(|(password, rule)| is_valid_toboggan_password(password, rule))
             .count();
-        number_of_valid_passwords.to_string()
+        Ok(number_of_valid_passwords.to_string())
     }
 }
 
-fn parse_password_data(line: &str) -> Option<(String, PasswordRule)>{
+fn parse_password_data(line: &str) -> Result<(String, PasswordRule), ParseError>{
     lazy_static! {
         static ref PASSWORD_RE: regex::Regex = regex::Regex::new(r"^(\d+)-(\d+) (\w): (.+)$").unwrap();
     }
-    let captures: regex::Captures = PASSWORD_RE.captures(line)?;
+    let captures: regex::Captures = PASSWORD_RE.captures(line)
+        .ok_or_else(|| ParseError::in_input("Expected a password entry of the form '<min>-<max> <character>: <password>'."))?;
     let password = captures[4].to_owned();
-    let character = captures[3].chars().next()?;
-    let min_number = captures[1].parse::<usize>().ok()?;
-    let max_number = captures[2].parse::<usize>().ok()?;
+    let character = captures[3].chars().next().unwrap(); //The regex guarantees exactly one character.
+    let min_number = parse_number::<usize>(&captures[1], captures.get(1).unwrap().start())?;
+    let max_number = parse_number::<usize>(&captures[2], captures.get(2).unwrap().start())?;
+    if min_number == 0 || min_number > max_number{
+        return Err(ParseError::in_line(0, "The positions in the password rule must satisfy 1 <= min <= max."));
+    }
     let rule = PasswordRule {character, min_number, max_number};
 
-    Some((password, rule))
+    Ok((password, rule))
 }
 
 fn is_valid_sled_password(password: &str, rule: &PasswordRule)-> bool {
@@ -66,7 +79,6 @@ fn is_valid_toboggan_password(password: &str, rule: &PasswordRule)-> bool {
 #[cfg(test)]
 mod day2_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -81,7 +93,7 @@ mod day2_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day2{});
         let problem_input = example_input();
         let expected_result = String::from("2");
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -90,25 +102,7 @@ mod day2_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day2{});
         let problem_input = example_input();
         let expected_result = String::from("1");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day2{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 2, part: 1}).unwrap();
-        let expected_result = String::from("500");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day2{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 2, part: 2}).unwrap();
-        let expected_result = String::from("313");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
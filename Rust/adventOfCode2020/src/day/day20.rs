@@ -1,9 +1,12 @@
-use std::{collections::{HashMap, HashSet}, iter::repeat};
+use std::{collections::{HashMap, HashSet}, iter};
 
-use crate::space::{Point, Vector};
+use crate::grid::{CoordinateRange, DenseGrid, Grid, MutGrid, OutsideBehaviour, TextCodec};
+use crate::space::{BoundingBox, Point, Vector, D4};
+use crate::error::{ParseError, PuzzleError};
 
 
 const MONSTER_CHARACTER: char = '#';
+const TILE_SIZE: usize = 10;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Border{
@@ -40,58 +43,66 @@ impl Border{
         (self.value() + 2).into()
     }
 
-    fn rotate(self, steps: u8) -> Border{
-        (self.value() + steps).into()
+    ///
+    /// The direction of the border in image coordinates, in which the y-axis points down.
+    ///
+    fn direction(self) -> Vector<i64, 2>{
+        match self{
+            Border::Upper => Vector::new([0, -1]),
+            Border::Right => Vector::new([1, 0]),
+            Border::Lower => Vector::new([0, 1]),
+            Border::Left => Vector::new([-1, 0]),
+        }
     }
 
-    fn flip_on_y_axis(self) -> Border{
-        let value = self.value();
-        if value % 2 == 0{
-            self
-        } else {
-            (value + 2).into()
-        }
+    fn transformed(self, orientation: Orientation) -> Border{
+        let direction = orientation.apply(self.direction());
+        [Border::Upper, Border::Right, Border::Lower, Border::Left].iter()
+            .copied()
+            .find(|border| border.direction() == direction)
+            .expect("Symmetries of the square map borders to borders.")
     }
 }
 
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Tile{
     id: u64,
-    image: [[bool;10];10],
+    image: DenseGrid<usize, bool, 2>,
     border_codes: [u16;4],
     flip_border_codes: [u16;4],
 }
 
 impl Tile{
-    pub fn new(id: u64, image: [[bool;10];10]) -> Tile{
+    pub fn new(id: u64, image: DenseGrid<usize, bool, 2>) -> Tile{
         let mut border_codes = [0;4];
         let mut flip_border_codes = [0;4];
 
-        border_codes[Border::Upper as usize] = to_bismask(image[0].iter().copied());
-        flip_border_codes[Border::Upper as usize] = to_bismask(image[0].iter().copied().rev());
+        let upper_border = border_values(&image.slice(1, 0));
+        let right_border = border_values(&image.slice(0, TILE_SIZE - 1));
+        let lower_border = border_values(&image.slice(1, TILE_SIZE - 1));
+        let left_border = border_values(&image.slice(0, 0));
+
+        border_codes[Border::Upper as usize] = to_bismask(upper_border.iter().copied());
+        flip_border_codes[Border::Upper as usize] = to_bismask(upper_border.iter().copied().rev());
 
-        border_codes[Border::Right as usize] = to_bismask(image.iter().map(|row| row[9]));
-        flip_border_codes[Border::Right as usize] = to_bismask(image.iter().map(|row| row[9]).rev());
+        border_codes[Border::Right as usize] = to_bismask(right_border.iter().copied());
+        flip_border_codes[Border::Right as usize] = to_bismask(right_border.iter().copied().rev());
 
-        border_codes[Border::Lower as usize] = to_bismask(image[9].iter().copied().rev());
-        flip_border_codes[Border::Lower as usize] = to_bismask(image[9].iter().copied());
+        border_codes[Border::Lower as usize] = to_bismask(lower_border.iter().copied().rev());
+        flip_border_codes[Border::Lower as usize] = to_bismask(lower_border.iter().copied());
 
-        border_codes[Border::Left as usize] = to_bismask(image.iter().map(|row| row[0]).rev());
-        flip_border_codes[Border::Left as usize] = to_bismask(image.iter().map(|row| row[0]));
+        border_codes[Border::Left as usize] = to_bismask(left_border.iter().copied().rev());
+        flip_border_codes[Border::Left as usize] = to_bismask(left_border.iter().copied());
 
         Tile {id, image, border_codes, flip_border_codes}
     }
+}
 
-    pub fn is_monster(&self, point: Point<usize, 2>) -> bool{
-        let x = point[0];
-        let y = point[1];
-        if x >= 10 || y >= 10 {
-            false
-        } else {
-            self.image[y][x]
-        }
-    }
+fn border_values(border: &impl Grid<bool, 2, CoordinateType = usize>) -> Vec<bool>{
+    border.cells()
+        .map(|(_, value)| *value)
+        .collect()
 }
 
 fn to_bismask(seq: impl Iterator<Item = bool>) -> u16{
@@ -116,73 +127,87 @@ pub struct SeaMonsterMask{
 
 pub struct Day20 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 20,
+    name: "Jurassic Jigsaw",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day20 {}),
+};
+
 impl super::Day for Day20{
     type PuzzleInput = (HashMap<u64, Tile>, SeaMonsterMask);
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
         let parts: Vec<&str> = text.split("\n\n").collect();
-        let sea_monster = parse_sea_monster_mask(parts[0]);
-        let tiles: HashMap<u64, Tile> = parts[1..].iter()
-            .map(|text| parse_tile(text))
-            .map(|tile| (tile.id, tile))
-            .collect();
-        (tiles, sea_monster)
+        let sea_monster = parse_sea_monster_mask(parts[0])?;
+        let mut tiles: HashMap<u64, Tile> = HashMap::new();
+        let mut line_offset = parts[0].lines().count() + 1;
+        for tile_text in parts[1..].iter(){
+            if !tile_text.trim().is_empty(){
+                let tile = parse_tile(tile_text).map_err(|error| error.offset_lines(line_offset))?;
+                tiles.insert(tile.id, tile);
+            }
+            line_offset += tile_text.lines().count() + 1;
+        }
+        Ok((tiles, sea_monster))
     }
 
-    fn solve_part1(&self, data: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, data: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let (tiles, _) = data;
         let ids_by_border_code = tiles_by_border_code(&tiles);
         let border_ids_with_border_codes = border_tiles_with_border_codes(&ids_by_border_code);
         let corner_ids = corner_ids(&border_ids_with_border_codes);
+        if corner_ids.len() != 4{
+            return Err(PuzzleError::unsolvable("The tiles do not have exactly four corners."));
+        }
         let result: u64 = corner_ids.iter().product();
-        result.to_string()
+        Ok(result.to_string())
     }
 
-    fn solve_part2(&self, data: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, data: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let (tiles, mask) = data;
-        let image = assembled_image(&tiles);
-        let monster_pixel_count = image.iter()
-            .flat_map(|row| row.iter())
-            .filter(|pixel| **pixel)
+        let image = assembled_image(&tiles)?;
+        let monster_pixel_count = image.cells()
+            .filter(|(_, pixel)| **pixel)
             .count();
         let pixels_in_monsters = pixels_with_monster(&image, &mask);
         let pixel_with_monster_count = pixels_in_monsters.len();
         let result = monster_pixel_count - pixel_with_monster_count;
-        result.to_string()
+        Ok(result.to_string())
     }
 }
 
-fn parse_sea_monster_mask(text: &str) -> SeaMonsterMask{
+fn parse_sea_monster_mask(text: &str) -> Result<SeaMonsterMask, ParseError>{
     let height = text.lines().count();
-    let width = text.lines().next().unwrap().len();
+    let width = text.lines().map(|line| line.len()).max()
+        .ok_or_else(|| ParseError::in_input("The sea monster is missing."))?;
     let monster_points = text.lines().enumerate()
         .flat_map(|(row, line )| line.chars()
             .enumerate()
             .filter_map(move |(column, c)| if c == MONSTER_CHARACTER { Some(Vector::new([column, row])) } else  { None } ))
         .collect();
-    SeaMonsterMask {width, height, monster_points}
+    Ok(SeaMonsterMask {width, height, monster_points})
 }
 
-fn parse_tile(text: &str) -> Tile{
+///
+/// Parses a tile. Line numbers in errors are relative to the start of the tile.
+///
+fn parse_tile(text: &str) -> Result<Tile, ParseError>{
     let lines: Vec<&str> = text.lines().collect();
-    let id = parse_tile_id(lines[0]).unwrap();
-    if lines.len() != 10 + 1{
-        panic!("Each tile must be 10x10!");
-    }
-    let mut image = [[false;10];10];
-
-    for (row, line) in lines[1..].iter().enumerate(){
-        if line.len() != 10{
-            panic!("Each tile must be 10x10!");
-        }
-        for (column, c) in line.chars().enumerate(){
-            if c == MONSTER_CHARACTER{
-                image[row][column] = true;
-            }
-        }
-    }
-
-    Tile::new(id, image)
+    let id = parse_tile_id(lines[0])
+        .ok_or_else(|| ParseError::at(0, 0, "Expected a tile header of the form 'Tile <id>:'."))?;
+    if lines.len() != TILE_SIZE + 1{
+        return Err(ParseError::in_input("Each tile must be 10x10!").on_line(0));
+    }
+    if let Some(row) = lines[1..].iter().position(|line| line.len() != TILE_SIZE){
+        return Err(ParseError::in_input("Each tile must be 10x10!").on_line(row + 1));
+    }
+    let codec = TextCodec::new(vec![(MONSTER_CHARACTER, true), ('.', false)]);
+    let image = codec.parse(&lines[1..].join("\n"), false, OutsideBehaviour::Default)
+        .map_err(|error| error.offset_lines(1))?;
+    Ok(Tile::new(id, image))
 }
 
 fn parse_tile_id(text: &str) -> Option<u64>{
@@ -195,62 +220,10 @@ fn parse_tile_id(text: &str) -> Option<u64>{
 }
 
 ///
-/// Orientation of a tile relative to its recorded form.
-///
-/// * `rotation` - Number of 90 degree rotations in counter-clockwise direction.
-/// * `flip` - Indicatore whether a the tile has been mirrored along the y-axis before rotation.
+/// Orientation of a tile relative to its recorded form, decomposed into a reflection
+/// along the y-axis followed by counter-clockwise quarter turns.
 ///
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Orientation{
-    rotation: u8,
-    flip: bool,
-}
-
-impl Orientation{
-    ///
-    /// Returns an orientation such that the operations revert those corresponding to self.
-    ///
-    #[allow(dead_code)]
-    fn reverse(&self) -> Orientation{
-        if self.flip {
-            Orientation { rotation: self.rotation(), flip: true}
-        } else {
-            let rotation = (4 - self.rotation()) % 4;
-            Orientation { rotation, flip: false}
-        }
-    }
-
-    fn possible_values() -> Vec<Orientation>{
-        let mut possibilities = vec![];
-        for rotation in 0..4{
-            possibilities.push(Orientation {rotation, flip:true});
-            possibilities.push(Orientation {rotation, flip:false});
-        }
-        possibilities
-    }
-
-    pub fn rotation(&self) -> u8{
-        self.rotation % 4
-    }
-
-    pub fn flip(&self) -> bool{
-        self.flip
-    }
-}
-
-fn reoriented_coordinates(point: Point<usize, 2>, orientation: &Orientation, width: usize, height: usize) -> Point<usize, 2>{
-    let rotation_steps = orientation.rotation();
-    let x_after_flip = if orientation.flip { width - point[0] - 1 } else { point[0] };
-    let y_after_flip = point[1];
-    match rotation_steps{
-        0 => Point::new([x_after_flip, y_after_flip]),
-        1 => Point::new([height - y_after_flip - 1, x_after_flip]),
-        2 => Point::new([width - x_after_flip - 1, height - y_after_flip - 1]),
-        3 => Point::new([y_after_flip, width - x_after_flip - 1]),
-        _ => unreachable!("Modulo 4 cannot yield this value."),
-    }
-}
-
+type Orientation = D4;
 
 fn tiles_by_border_code(tiles_by_id: &HashMap<u64, Tile>) -> HashMap<u16, Vec<(u64, Orientation)>>{
     let mut ids_by_border_code = HashMap::<u16, Vec<(u64, Orientation)>>::new();
@@ -259,10 +232,10 @@ fn tiles_by_border_code(tiles_by_id: &HashMap<u64, Tile>) -> HashMap<u16, Vec<(u
             let tile_id = tile.id;
             tile.border_codes.iter()
                 .enumerate()
-                .map(|(direction, code)| (code, Orientation {rotation: (direction % 4) as u8, flip:false}))
+                .map(|(direction, code)| (code, Orientation::from_reflection_and_rotation(false, (direction % 4) as u8)))
             .chain(tile.flip_border_codes.iter()
                 .enumerate()
-                .map(|(direction, code)| (code, Orientation {rotation: (direction % 4) as u8, flip:true})))
+                .map(|(direction, code)| (code, Orientation::from_reflection_and_rotation(true, (direction % 4) as u8))))
                     .map(move |(code, or)| (*code, (tile_id, or)))
         }){
         match ids_by_border_code.get_mut(&code){
@@ -305,47 +278,52 @@ fn outer_border_codes_with_tiles(ids_by_border_code: &HashMap::<u16, Vec<(u64, O
 }
 
 
-fn assembled_image(tiles_by_id: &HashMap<u64, Tile>) -> Vec<Vec<bool>>{
-    let arranged_tiles = assembled_tiles(tiles_by_id);
-    let mut rows = vec![];
-    for tile_row in arranged_tiles.iter(){
-        let mut row_batch: Vec<Vec<bool>> = repeat(vec![]).take(8).collect();
-        for (tile_id, tile_or) in tile_row.iter(){
+fn assembled_image(tiles_by_id: &HashMap<u64, Tile>) -> Result<DenseGrid<usize, bool, 2>, PuzzleError>{
+    let arranged_tiles = assembled_tiles(tiles_by_id)?;
+    let interior_size = TILE_SIZE - 2;
+    let coordinate_ranges = [
+        CoordinateRange {lower_bound: 0, upper_bound: arranged_tiles.first().map_or(0, |row| row.len()) * interior_size},
+        CoordinateRange {lower_bound: 0, upper_bound: arranged_tiles.len() * interior_size},
+    ];
+    let mut image = DenseGrid::new(coordinate_ranges, false, OutsideBehaviour::Default, iter::empty());
+    let interior = BoundingBox::new(Point::new([1, 1]), Point::new([TILE_SIZE - 1, TILE_SIZE - 1]));
+    for (row_index, tile_row) in arranged_tiles.iter().enumerate(){
+        for (column_index, (tile_id, tile_or)) in tile_row.iter().enumerate(){
             let tile = tiles_by_id.get(tile_id).unwrap();
-            for y in 1..9{
-                for x in 1..9{
-                    let lookup_point = reoriented_coordinates(Point::new([x,y]), tile_or,10, 10);
-                    row_batch[y-1].push(tile.is_monster(lookup_point));
-                }
+            let reoriented_tile = tile.image.transformed(tile_or.inverse());
+            let tile_offset = Vector::new([column_index * interior_size, row_index * interior_size]);
+            for (point, pixel) in reoriented_tile.cropped(interior).cells(){
+                image.set_point(&(point - Vector::new([1, 1]) + tile_offset), *pixel);
             }
         }
-        rows.append(&mut row_batch);
     }
-    rows
+    Ok(image)
 }
 
 ///Returns an arrangement of the tiles with fitting borders between the tiles.
 ///The orientation describes the transformation from the tile in the assembled image to the original tile.
-fn assembled_tiles(tiles_by_id: &HashMap<u64, Tile>) -> Vec<Vec<(u64, Orientation)>>{
+fn assembled_tiles(tiles_by_id: &HashMap<u64, Tile>) -> Result<Vec<Vec<(u64, Orientation)>>, PuzzleError>{
     let mut rows = vec![];
     let ids_by_border_code = tiles_by_border_code(&tiles_by_id);
     let border_ids_with_border_codes = border_tiles_with_border_codes(&ids_by_border_code);
     let mut corner_ids = corner_ids(&border_ids_with_border_codes);
+    if corner_ids.len() != 4{
+        return Err(PuzzleError::unsolvable("The tiles do not have exactly four corners."));
+    }
     corner_ids.sort_unstable(); //This is here to make the results deterministic.
     let start_corner = corner_ids[0];
-    let first_row = arranged_first_row(start_corner, &tiles_by_id, &ids_by_border_code, &border_ids_with_border_codes);
+    let first_row = arranged_first_row(start_corner, &tiles_by_id, &ids_by_border_code, &border_ids_with_border_codes)?;
 
     let mut maybe_current_row = Some(first_row);
-    while maybe_current_row.is_some(){
-        let current_row = maybe_current_row.unwrap();
-        let maybe_next_row: Option<Vec<(u64, Orientation)>> = current_row.iter()
+    while let Some(current_row) = maybe_current_row{
+        let next_tiles: Vec<Option<(u64, Orientation)>> = current_row.iter()
             .map(|(id, or)| tile_in_direction(Border::Lower, *id, or.to_owned(), tiles_by_id, &ids_by_border_code))
-            .collect();
+            .collect::<Result<_, PuzzleError>>()?;
         rows.push(current_row);
-        maybe_current_row = maybe_next_row;
+        maybe_current_row = next_tiles.into_iter().collect();
     }
 
-    rows
+    Ok(rows)
 }
 
 fn arranged_first_row(
@@ -353,8 +331,8 @@ fn arranged_first_row(
     tiles_by_id: &HashMap<u64, Tile>,
     ids_by_border_code: &HashMap<u16, Vec<(u64, Orientation)>>,
     border_ids_with_border_codes: &HashMap<u64, Vec<(u16, Orientation)>>
-) -> Vec<(u64, Orientation)>{
-    let start_corner_orientation = upper_left_corner_orientation(start_corner_id, border_ids_with_border_codes);
+) -> Result<Vec<(u64, Orientation)>, PuzzleError>{
+    let start_corner_orientation = upper_left_corner_orientation(start_corner_id, border_ids_with_border_codes)?;
     let mut first_row = vec![];
     let mut maybe_current_tile = Some((start_corner_id, start_corner_orientation));
     while let Some(current_tile) = maybe_current_tile{
@@ -365,110 +343,98 @@ fn arranged_first_row(
             current_id,
             current_or,
             tiles_by_id,
-            ids_by_border_code);
+            ids_by_border_code)?;
     }
 
-    first_row
+    Ok(first_row)
 }
 
-fn upper_left_corner_orientation(corner_id: u64, boder_ids_with_border_codes: &HashMap<u64, Vec<(u16, Orientation)>>) -> Orientation{
+fn upper_left_corner_orientation(corner_id: u64, boder_ids_with_border_codes: &HashMap<u64, Vec<(u16, Orientation)>>) -> Result<Orientation, PuzzleError>{
     let mut non_flip_rotations: Vec<u8> = boder_ids_with_border_codes.get(&corner_id).unwrap()
         .iter()
-        .filter(|(_, or)| !or.flip)
-        .map(|(_, or)| or.rotation())
+        .filter(|(_, or)| !or.is_reflection())
+        .map(|(_, or)| or.quarter_turns())
         .collect();
     if non_flip_rotations.len() != 2{
-        panic!("Unexpected number of non-flip border codes!")
+        return Err(PuzzleError::unsolvable("Unexpected number of non-flip border codes!"));
     }
     non_flip_rotations.sort_unstable();
     if non_flip_rotations[1] == (Border::Left.into())
         &&  non_flip_rotations[0] == (Border::Upper.into()){
-            Orientation { rotation: 0, flip: false }
+            Ok(Orientation::identity())
     } else {
-        Orientation { rotation: non_flip_rotations[0] + 1, flip: false }
+        Ok(Orientation::from_reflection_and_rotation(false, non_flip_rotations[0] + 1))
     }
 }
 
-/// Returns the next tile in the direction together with its orientation.
+/// Returns the next tile in the direction together with its orientation, or `None` at the edge of the image.
 fn tile_in_direction(
     direction: Border,
     tile_id: u64,
     orientation: Orientation,
     tiles_by_id: &HashMap<u64, Tile>,
     ids_by_border_code: &HashMap<u16, Vec<(u64, Orientation)>>,
-) -> Option<(u64, Orientation)>{
+) -> Result<Option<(u64, Orientation)>, PuzzleError>{
     let border_code = flip_border_code_in_direction(direction, tile_id, orientation, tiles_by_id);
-    let potential_tiles = ids_by_border_code.get(&border_code)?;
+    let potential_tiles = match ids_by_border_code.get(&border_code){
+        Some(potential_tiles) => potential_tiles,
+        None => return Ok(None),
+    };
     let other_tiles: Vec<&(u64, Orientation)> = potential_tiles.iter()
         .filter(|(id, _or)| *id != tile_id)
         .collect();
     if other_tiles.is_empty(){
-        return None;
+        return Ok(None);
     }
     if other_tiles.len() > 1{
-        panic!("The edges of the tiles are not unique!");
+        return Err(PuzzleError::unsolvable("The edges of the tiles are not unique!"));
     }
     let (next_tile, border_or) = other_tiles[0];
 
     let start_border = direction.opposite();
-    let start_border_after_potential_flip = if border_or.flip() { start_border.flip_on_y_axis() } else { start_border };
-    let rotation = (border_or.rotation() + 4 - start_border_after_potential_flip.value()) % 4;
-    let tile_or = Orientation { rotation, flip: border_or.flip()};
+    let start_border_after_potential_flip = if border_or.is_reflection() { start_border.transformed(Orientation::reflection_on_y_axis()) } else { start_border };
+    let rotation = (border_or.quarter_turns() + 4 - start_border_after_potential_flip.value()) % 4;
+    let tile_or = Orientation::from_reflection_and_rotation(border_or.is_reflection(), rotation);
 
-    Some((*next_tile, tile_or))
-}
-
-fn border_after_orientation(border: Border, orientation: Orientation) -> Border{
-    let border_after_potential_flip = if orientation.flip() { border.flip_on_y_axis() } else { border };
-    border_after_potential_flip.rotate(orientation.rotation())
+    Ok(Some((*next_tile, tile_or)))
 }
 
 fn flip_border_code_in_direction(direction: Border, tile_id: u64, orientation: Orientation, tiles_by_id: &HashMap<u64, Tile>) -> u16{
     let tile = tiles_by_id.get(&tile_id).unwrap();
-    let border_on_tile = border_after_orientation(direction, orientation);
-    if orientation.flip{
+    let border_on_tile = direction.transformed(orientation);
+    if orientation.is_reflection(){
         tile.border_codes[border_on_tile as usize]
     } else {
         tile.flip_border_codes[border_on_tile as usize]
     }
 }
 
-fn pixels_with_monster(image: &[Vec<bool>], mask: &SeaMonsterMask) -> HashSet<Point<usize, 2>>{
-    Orientation::possible_values().iter()
-        .flat_map(|or| pixels_with_monster_in_orientation(image, mask, or))
-        .collect()
-}
-
-fn pixels_with_monster_in_orientation(image: &[Vec<bool>], mask: &SeaMonsterMask, orientation: &Orientation) -> Vec<Point<usize, 2>>{
-    let image_height = image.len();
-    if image_height == 0 {
-        return vec![];
+fn pixels_with_monster(image: &DenseGrid<usize, bool, 2>, mask: &SeaMonsterMask) -> HashSet<Point<usize, 2>>{
+    let mut pixels = HashSet::new();
+    for orientation in Orientation::all(){
+        let reoriented_image = image.transformed(orientation);
+        for point in pixels_with_monster_in_view(&reoriented_image, mask){
+            pixels.insert(reoriented_image.underlying_point(&point));
+        }
     }
-    let image_width = image[0].len();
-
-    let transformed_mask_height = if orientation.rotation % 2 == 0 { mask.height } else { mask.width };
-    let transformed_mask_width = if orientation.rotation % 2 == 0 { mask.width } else { mask.height };
+    pixels
+}
 
-    if transformed_mask_width > image_width || transformed_mask_height > image_height{
+fn pixels_with_monster_in_view(image: &impl Grid<bool, 2, CoordinateType = usize>, mask: &SeaMonsterMask) -> Vec<Point<usize, 2>>{
+    let [image_width, image_height] = image.bounding_box().size();
+    if mask.width > image_width || mask.height > image_height{
         return vec![];
     }
 
-    let mask_base_point = Point::new([0,0]);
-    let reoriented_mask: HashSet<Vector<usize, 2>> = mask.monster_points.iter()
-        .map(|offset| reoriented_coordinates(mask_base_point + *offset, orientation, mask.width, mask.height) - mask_base_point)
-        .collect();
-
-    let base_points = (0..(image_width - transformed_mask_width))
-        .flat_map(|x| (0..(image_height - transformed_mask_height))
-            .map(move |y| Point::new([x, y])));
+    let base_points = BoundingBox::new(Point::new([0, 0]), Point::new([image_width - mask.width + 1, image_height - mask.height + 1]))
+        .points();
 
     let base_points_with_monster = base_points
-        .filter(|point| reoriented_mask.iter()
-            .map(|offset| *point + *offset)
-            .all(|point| image[point[1]][point[0]]));
+        .filter(|point| mask.monster_points.iter()
+            .all(|offset| *image.at_point(&(*point + *offset))));
 
     base_points_with_monster
-        .flat_map(|point| reoriented_mask.iter()
+        .flat_map(|point| mask.monster_points.iter()
             .map(move |offset| point + *offset))
         .collect()
 }
@@ -477,7 +443,6 @@ fn pixels_with_monster_in_orientation(image: &[Vec<bool>], mask: &SeaMonsterMask
 #[cfg(test)]
 mod day20_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -600,10 +565,19 @@ Tile 3079:
         let day: Box<dyn day::DaySolver> = Box::new(Day20{});
         let problem_input = example_input();
         let expected_result = 20899048083289u64.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
+    #[test]
+    fn tiles_without_corners_are_unsolvable() {
+        let day: Box<dyn day::DaySolver> = Box::new(Day20{});
+        let example_input = example_input();
+        let single_tile_input = &example_input[..example_input.find("\n\nTile 1951").unwrap()];
+        assert!(matches!(day.solve_part1(String::from(single_tile_input)), Err(PuzzleError::Unsolvable(_))));
+        assert!(matches!(day.solve_part2(String::from(single_tile_input)), Err(PuzzleError::Unsolvable(_))));
+    }
+
     #[test]
     fn example_part2() {
         let day: Box<dyn day::DaySolver> = Box::new(Day20{});
@@ -635,25 +609,7 @@ Tile 3079:
 #..###....##.#...##.##.#"
             .chars().filter(|c| *c == MONSTER_CHARACTER).count();
         let expected_result = expected_value.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day20{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 20, part: 1}).unwrap();
-        let expected_result = String::from("63187742854073");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day20{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 20, part: 2}).unwrap();
-        let expected_result = String::from("2152");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
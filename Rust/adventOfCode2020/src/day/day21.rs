@@ -1,5 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::error::{ParseError, PuzzleError};
+use super::parse_lines;
+
 
 
 #[derive(Debug, Clone)]
@@ -11,41 +14,50 @@ pub struct Recipe{
 
 pub struct Day21 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 21,
+    name: "Allergen Assessment",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day21 {}),
+};
+
 impl super::Day for Day21{
     type PuzzleInput = Vec<Recipe>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .filter_map(|line| parse_recipe(line))
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let recipes = parse_lines(&text, parse_recipe)?;
+        Ok(recipes)
     }
 
-    fn solve_part1(&self, recipies: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, recipies: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let ingredients_with_allergens: HashSet<String> = ingredients_with_allergens(&recipies).values().cloned().collect();
         let result = recipies.iter()
             .flat_map(|recipe| recipe.ingredients.iter())
             .filter(|ingredient| !ingredients_with_allergens.contains(*ingredient))
             .count();
-        result.to_string()
+        Ok(result.to_string())
     }
 
-    fn solve_part2(&self, recipies: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, recipies: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let ingredients_with_allergens = ingredients_with_allergens(&recipies);
         let mut allergens: Vec<String> = ingredients_with_allergens.keys().cloned().collect();
         allergens.sort_unstable();
         let ingredients_sorted_by_allergens: Vec<String> = allergens.iter().map(|allergen| ingredients_with_allergens.get(allergen).unwrap().to_owned()).collect();
-        ingredients_sorted_by_allergens.join(",")
+        Ok(ingredients_sorted_by_allergens.join(","))
     }
 }
 
-fn parse_recipe(text: &str) -> Option<Recipe>{
+fn parse_recipe(text: &str) -> Result<Recipe, ParseError>{
     lazy_static! {
         static ref RECIPE_RE: regex::Regex = regex::Regex::new(r#"(\w+( \w+)*) \(contains (\w+(, \w+)*)\)"#).unwrap();
     }
-    let captures: regex::Captures = RECIPE_RE.captures(text)?;
+    let captures: regex::Captures = RECIPE_RE.captures(text)
+        .ok_or_else(|| ParseError::in_input("Expected a recipe of the form '<ingredients> (contains <allergens>)'."))?;
     let ingredients = captures[1].split(' ').map(|ingredient| ingredient.to_owned()).collect();
     let allergens = captures[3].split(", ").map(|allergens| allergens.to_owned()).collect();
-    Some(Recipe { ingredients, allergens })
+    Ok(Recipe { ingredients, allergens })
 }
 
 fn ingredients_with_allergens(recipes: &[Recipe]) -> HashMap<String, String>{
@@ -122,7 +134,6 @@ fn common_ingredients(recipies: &[Recipe], selected_indices: &[usize]) -> HashSe
 #[cfg(test)]
 mod day21_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -138,7 +149,7 @@ sqjhc mxmxvkd sbzzf (contains fish)"#)
         let day: Box<dyn day::DaySolver> = Box::new(Day21{});
         let problem_input = example_input();
         let expected_result = 5.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -147,25 +158,7 @@ sqjhc mxmxvkd sbzzf (contains fish)"#)
         let day: Box<dyn day::DaySolver> = Box::new(Day21{});
         let problem_input = example_input();
         let expected_result = String::from("mxmxvkd,sqjhc,fvjkl");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day21{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 21, part: 1}).unwrap();
-        let expected_result = String::from("2265");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day21{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 21, part: 2}).unwrap();
-        let expected_result = String::from("dtb,zgk,pxr,cqnl,xkclg,xtzh,jpnv,lsvlx");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
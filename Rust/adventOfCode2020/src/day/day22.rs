@@ -1,38 +1,61 @@
 use std::collections::{HashSet, VecDeque};
 
+use crate::error::{ParseError, PuzzleError};
+use super::parse_number;
+
 
 
 
 pub struct Day22 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 22,
+    name: "Crab Combat",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day22 {}),
+};
+
 impl super::Day for Day22{
     type PuzzleInput = Vec<VecDeque<u8>>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.split("\n\n")
-            .map(|part| parse_deck(part))
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let mut decks = vec![];
+        let mut line_offset = 0;
+        for part in text.split("\n\n"){
+            decks.push(parse_deck(part).map_err(|error| error.offset_lines(line_offset))?);
+            line_offset += part.lines().count() + 1;
+        }
+        Ok(decks)
     }
 
-    fn solve_part1(&self, mut decks: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, mut decks: Self::PuzzleInput) -> Result<String, PuzzleError> {
         play_game(&mut decks);
         let scores = decks.iter().map(|deck| deck_score(deck));
-        let result = scores.max().unwrap();
-        result.to_string()
+        let result = scores.max()
+            .ok_or_else(|| PuzzleError::unsolvable("There are no players."))?;
+        Ok(result.to_string())
     }
 
-    fn solve_part2(&self, mut decks: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, mut decks: Self::PuzzleInput) -> Result<String, PuzzleError> {
         play_recursive_game(&mut decks);
         let scores = decks.iter().map(|deck| deck_score(deck));
-        let result = scores.max().unwrap();
-        result.to_string()
+        let result = scores.max()
+            .ok_or_else(|| PuzzleError::unsolvable("There are no players."))?;
+        Ok(result.to_string())
     }
 }
 
-fn parse_deck(text: &str) -> VecDeque<u8>{
+///
+/// Parses a deck. Line numbers in errors are relative to the start of the deck.
+///
+fn parse_deck(text: &str) -> Result<VecDeque<u8>, ParseError>{
     text.lines()
+        .enumerate()
         .skip(1)
-        .filter_map(|line| line.parse::<u8>().ok())
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line_index, line)| parse_number::<u8>(line, 0).map_err(|error| error.on_line(line_index)))
         .collect()
 }
 
@@ -168,7 +191,6 @@ fn evaluate_recursive_game(cards_played: Vec<u8>, recursive_decks: &[VecDeque<u8
 #[cfg(test)]
 mod day22_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -193,7 +215,7 @@ Player 2:
         let day: Box<dyn day::DaySolver> = Box::new(Day22{});
         let problem_input = example_input();
         let expected_result = 306.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -202,25 +224,7 @@ Player 2:
         let day: Box<dyn day::DaySolver> = Box::new(Day22{});
         let problem_input = example_input();
         let expected_result = 291.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day22{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 22, part: 1}).unwrap();
-        let expected_result = String::from("32033");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day22{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 22, part: 2}).unwrap();
-        let expected_result = String::from("34901");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 use std::cmp::max;
 use std::convert::TryFrom;
 
+use crate::error::{ParseError, PuzzleError};
+
 
 
 
@@ -8,21 +10,40 @@ use std::convert::TryFrom;
 
 pub struct Day23 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 23,
+    name: "Crab Cups",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day23 {}),
+};
+
 impl super::Day for Day23{
     type PuzzleInput = Vec<usize>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.trim().chars()
-            .filter_map(|c| if let Some(digit) = c.to_digit(10) { usize::try_from(digit).ok() } else { None })
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let cup_text = text.trim();
+        if cup_text.is_empty(){
+            return Err(ParseError::in_input("There are no cups.").into());
+        }
+        let mut cups = vec![];
+        for (index, c) in cup_text.chars().enumerate(){
+            let cup = c.to_digit(10)
+                .filter(|digit| *digit > 0)
+                .and_then(|digit| usize::try_from(digit).ok())
+                .ok_or_else(|| ParseError::at(0, index, &format!("'{}' is not a valid cup label.", c)))?;
+            cups.push(cup);
+        }
+        Ok(cups)
     }
 
-    fn solve_part1(&self, cups: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, cups: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let number_of_rounds = 100;
-        solve_part1_impl(&cups, number_of_rounds)
+        Ok(solve_part1_impl(&cups, number_of_rounds))
     }
 
-    fn solve_part2(&self, start_cups: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, start_cups: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let number_of_cups = 1000000;
         let number_of_rounds = 10000000;
         let game_results = play_crab_game(&start_cups, number_of_cups, number_of_rounds);
@@ -31,7 +52,7 @@ impl super::Day for Day23{
         let next_cup = game_results[start_cup - 1] + 1;
         let second_next_cup = game_results[next_cup - 1] + 1;
         let result = next_cup * second_next_cup;
-        result.to_string()
+        Ok(result.to_string())
     }
 }
 
@@ -130,7 +151,6 @@ fn cup_sequence(linked_cup_list: &[usize], start_cup: usize, sequence_length: us
 #[cfg(test)]
 mod day23_tests {
     use super::*;
-    use crate::input;
     use crate::day;
     use rstest::rstest;
 
@@ -154,7 +174,7 @@ r#"389125467"#)
     fn basic_examples_part1(#[case] number_of_rounds: usize, #[case] expected_result: String) {
         let day: Box<dyn day::Day<PuzzleInput = Vec<usize>>> = Box::new(Day23{});
         let problem_input = example_input();
-        let cups = day.parse_input(problem_input);
+        let cups = day.parse_input(problem_input).unwrap();
         let actual_result = super::solve_part1_impl(&cups, number_of_rounds);
         assert_eq!(actual_result, expected_result);
     }
@@ -164,7 +184,7 @@ r#"389125467"#)
         let day: Box<dyn day::DaySolver> = Box::new(Day23{});
         let problem_input = example_input();
         let expected_result = 67384529.to_string();
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -173,25 +193,7 @@ r#"389125467"#)
         let day: Box<dyn day::DaySolver> = Box::new(Day23{});
         let problem_input = example_input();
         let expected_result = 149245887792usize.to_string();
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day23{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 23, part: 1}).unwrap();
-        let expected_result = String::from("82934675");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day23{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 23, part: 2}).unwrap();
-        let expected_result = String::from("474600314018");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
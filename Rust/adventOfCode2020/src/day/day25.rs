@@ -1,3 +1,6 @@
+use crate::error::{ParseError, PuzzleError};
+use super::{parse_lines, parse_number};
+
 
 pub struct DoorCard{
     card_public_key: u64,
@@ -8,26 +11,39 @@ pub struct DoorCard{
 
 pub struct Day25 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 25,
+    name: "Combo Breaker",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day25 {}),
+};
+
 impl super::Day for Day25{
     type PuzzleInput = DoorCard;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        let keys: Vec<_> = text.lines().filter_map(|line| line.parse::<u64>().ok()).collect();
-        DoorCard {
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let keys = parse_lines(&text, |line| parse_number::<u64>(line, 0))?;
+        if keys.len() != 2{
+            return Err(ParseError::in_input("Expected exactly two public keys.").into());
+        }
+        Ok(DoorCard {
             card_public_key: keys[0],
             door_public_key: keys[1],
             base: 7,
             key_space_size: 20201227,
-        }
+        })
     }
 
-    fn solve_part1(&self, card: Self::PuzzleInput) -> std::string::String {
-        let key = encryption_key(&card).unwrap();
-        key.to_string()
+    fn solve_part1(&self, card: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        let key = encryption_key(&card)
+            .ok_or_else(|| PuzzleError::unsolvable("The loop size of the card could not be determined."))?;
+        Ok(key.to_string())
     }
 
-    fn solve_part2(&self, _: Self::PuzzleInput) -> std::string::String {
-        unimplemented!();
+    fn solve_part2(&self, _: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        Err(PuzzleError::unsolvable("There is no second part on the last day."))
     }
 }
 
@@ -63,7 +79,6 @@ fn transformed_key(key: u64, loop_size: u64, key_space_size: u64) -> u64{
 #[cfg(test)]
 mod day25_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -77,16 +92,7 @@ r#"5764801
         let day: Box<dyn day::DaySolver> = Box::new(Day25{});
         let problem_input = example_input();
         let expected_result = 14897079.to_string();
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day25{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 25, part: 1}).unwrap();
-        let expected_result = String::from("6421487");
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
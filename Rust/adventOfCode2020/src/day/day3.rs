@@ -1,68 +1,47 @@
 use crate::grid;
 use crate::space;
+use crate::error::{ParseError, PuzzleError};
 
 
 pub struct Day3 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 3,
+    name: "Toboggan Trajectory",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day3 {}),
+};
+
 impl super::Day for Day3{
-    type PuzzleInput = Option<grid::LoopingGrid<usize,bool,2>>;
+    type PuzzleInput = grid::DenseGrid<usize,bool,2>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        let lines = text.lines().collect::<Vec<&str>>();
-        let height = lines.len();
-        if height == 0{
-            return None;
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        if text.lines().next().is_none(){
+            return Err(ParseError::in_input("The map is empty.").into());
         }
-        let width = lines[0].len();
-        let points_with_trees = lines.iter()
-            .enumerate()
-            .map(|(y, line)| (y, tree_indices(&line)))
-            .map(|(y, xs)| xs.into_iter().map(move |x| space::Point::new([x,y])))
-            .flatten();
-        let coordinate_ranges = [
-            grid::CoordinateRange {lower_bound: 0, upper_bound: width},
-            grid::CoordinateRange {lower_bound: 0, upper_bound: height}
-        ];
-        let grid = grid::LoopingGrid::new(coordinate_ranges, false, points_with_trees.map(|point| (point, true)));
-        Some(grid)
+        let codec = grid::TextCodec::new(vec![('#', true), ('.', false)]);
+        let slope = codec.parse(&text, false, grid::OutsideBehaviour::Wrapping)?;
+        Ok(slope)
     }
 
-    fn solve_part1(&self, maybe_grid: Self::PuzzleInput) -> std::string::String {
-        if maybe_grid.is_none() {
-            return String::from("Invalid input!");
-        }
-        let slope = maybe_grid.unwrap();
+    fn solve_part1(&self, slope: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let start_point = space::Point::new([0usize,0usize]);
         let direction = space::Vector::new([3usize,1usize]);
         let tree_count = trees_in_direction(start_point, &direction, &slope);
-        tree_count.to_string()
+        Ok(tree_count.to_string())
     }
 
-    fn solve_part2(&self, maybe_grid: Self::PuzzleInput) -> std::string::String {
-        if maybe_grid.is_none() {
-            return String::from("Invalid input!");
-        }
-        let slope = maybe_grid.unwrap();
+    fn solve_part2(&self, slope: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let start_point = space::Point::new([0usize, 0usize]);
         let directions = [space::Vector::new([1usize, 1usize]), space::Vector::new([3usize, 1usize]), space::Vector::new([5usize, 1usize]), space::Vector::new([7usize, 1usize]), space::Vector::new([1usize, 2usize])];
         let tree_counts = directions.iter().map(|direction| trees_in_direction(start_point, direction, &slope));
         let result: usize = tree_counts.product();
-        result.to_string()
+        Ok(result.to_string())
     }
 }
 
-fn tree_indices(line: &str) -> Vec<usize>{
-    line.chars()
-        .enumerate()
-        .filter(|(_, value)| is_tree(value))
-        .map(|(index, _)| index)
-        .collect()
-}
-
-fn is_tree(c: &char) -> bool{
-    c == &'#'
-}
-
 fn trees_in_direction(start_point: space::Point<usize, 2>, direction: &space::Vector<usize, 2>, slope: &impl grid::Grid<bool, 2, CoordinateType=usize>) -> usize{
     let normalized_direction = direction.to_direction();
     trees_with_step(start_point, normalized_direction, slope)
@@ -86,7 +65,6 @@ fn trees_with_step(start_point: space::Point<usize, 2>, step: space::Vector<usiz
 #[cfg(test)]
 mod day3_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -109,7 +87,7 @@ mod day3_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day3{});
         let problem_input = example_input();
         let expected_result = String::from("7");
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -118,25 +96,7 @@ mod day3_tests {
         let day: Box<dyn day::DaySolver> = Box::new(Day3{});
         let problem_input = example_input();
         let expected_result = String::from("336");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day3{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 3, part: 1}).unwrap();
-        let expected_result = String::from("209");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day3{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 3, part: 2}).unwrap();
-        let expected_result = String::from("1574890240");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
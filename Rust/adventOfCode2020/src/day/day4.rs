@@ -1,5 +1,7 @@
 use regex::Regex;
 
+use crate::error::PuzzleError;
+
 
 #[derive(Eq, PartialEq, Hash, Debug)]
 pub struct TravelDocument{
@@ -17,27 +19,37 @@ pub struct TravelDocument{
 
 pub struct Day4 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 4,
+    name: "Passport Processing",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day4 {}),
+};
+
 impl super::Day for Day4{
     type PuzzleInput = Vec<TravelDocument>;
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
         let document_delimiter_regex = regex::Regex::new(r"\r?\n\r?\n").unwrap();
-        document_delimiter_regex.split(&text)
+        let documents = document_delimiter_regex.split(&text)
             .map(|document_text| parse_document(document_text))
-            .collect()
+            .collect();
+        Ok(documents)
     }
 
-    fn solve_part1(&self, documents: Self::PuzzleInput) -> String {
+    fn solve_part1(&self, documents: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let number_of_passports = documents.iter()
             .filter(|document| is_passport(document))
             .count();
-        number_of_passports.to_string()
+        Ok(number_of_passports.to_string())
     }
 
-    fn solve_part2(&self, documents: Self::PuzzleInput) -> String {
+    fn solve_part2(&self, documents: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let number_of_passports = documents.iter()
             .filter(|document| is_valid_passport(document))
             .count();
-        number_of_passports.to_string()
+        Ok(number_of_passports.to_string())
     }
 
 }
@@ -182,7 +194,6 @@ fn is_valid_passport_id(text: &str) -> bool{
 #[cfg(test)]
 mod day4_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
 
@@ -241,7 +252,7 @@ pid:3556412378 byr:2007")
         let day: Box<dyn day::DaySolver> = Box::new(Day4{});
         let problem_input = example_input();
         let expected_result = String::from("2");
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -250,7 +261,7 @@ pid:3556412378 byr:2007")
         let day: Box<dyn day::DaySolver> = Box::new(Day4{});
         let problem_input = example_valid();
         let expected_result = String::from("4");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -259,24 +270,7 @@ pid:3556412378 byr:2007")
         let day: Box<dyn day::DaySolver> = Box::new(Day4{});
         let problem_input = example_invalid();
         let expected_result = String::from("0");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day4{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 4, part: 1}).unwrap();
-        let expected_result = String::from("182");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day4{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 4, part: 2}).unwrap();
-        let expected_result = String::from("109");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
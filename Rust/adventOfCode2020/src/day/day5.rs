@@ -1,3 +1,5 @@
+use crate::error::{ParseError, PuzzleError};
+use super::parse_lines;
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub struct Seat{
@@ -8,44 +10,69 @@ pub struct Seat{
 
 pub struct Day5 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 5,
+    name: "Binary Boarding",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day5 {}),
+};
+
 impl super::Day for Day5{
     type PuzzleInput = Vec<Seat>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .map(|boarding_card_text| parse_boarding_card(boarding_card_text))
-            .filter_map(Result::ok)
-            .collect::<Vec<Seat>>()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let seats = parse_lines(&text, parse_boarding_card)?;
+        Ok(seats)
     }
 
-    fn solve_part1(&self, seats: Self::PuzzleInput) -> std::string::String {
-        seats.iter()
+    fn solve_part1(&self, seats: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        let highest_seat_id = seats.iter()
             .map(|seat| seat.seat_id())
-            .fold(0, |previous_max, next_value| previous_max.max(next_value))
-            .to_string()
+            .fold(0, |previous_max, next_value| previous_max.max(next_value));
+        Ok(highest_seat_id.to_string())
     }
 
-    fn solve_part2(&self, seats: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, seats: Self::PuzzleInput) -> Result<String, PuzzleError> {
+        if seats.is_empty(){
+            return Err(PuzzleError::unsolvable("There are no occupied seats."));
+        }
         let my_seat_id = first_free_seat_id(&seats);
-        my_seat_id.to_string()
+        Ok(my_seat_id.to_string())
     }
 }
 
-fn parse_boarding_card(text: &str) -> Result<Seat, std::num::ParseIntError>{
+fn parse_boarding_card(text: &str) -> Result<Seat, ParseError>{
+    if text.len() != 10 || !text.is_char_boundary(7){
+        return Err(ParseError::in_input("A boarding card must consist of 10 characters."));
+    }
     let (row_text, column_text) = text.split_at(7);
     let row = parse_seat_row(row_text)?;
-    let column = parse_seat_column(column_text)?;
+    let column = parse_seat_column(column_text)
+        .map_err(|error| ParseError::in_line(error.column + 6, &error.message))?;
     Ok(Seat {row, column})
 }
 
-fn parse_seat_row(text: &str) -> Result<u16, std::num::ParseIntError>{
-    let binary_representation = text.replace("F", "0").replace("B", "1");
-    u16::from_str_radix(&binary_representation, 2)
+fn parse_seat_row(text: &str) -> Result<u16, ParseError>{
+    parse_binary_partition(text, 'F', 'B')
 }
 
-fn parse_seat_column(text: &str) -> Result<u16, std::num::ParseIntError>{
-    let binary_representation = text.replace("L", "0").replace("R", "1");
-    u16::from_str_radix(&binary_representation, 2)
+fn parse_seat_column(text: &str) -> Result<u16, ParseError>{
+    parse_binary_partition(text, 'L', 'R')
+}
+
+fn parse_binary_partition(text: &str, lower_half: char, upper_half: char) -> Result<u16, ParseError>{
+    let mut value = 0;
+    for (index, c) in text.chars().enumerate(){
+        value <<= 1;
+        if c == upper_half{
+            value += 1;
+        } else if c != lower_half{
+            return Err(ParseError::in_line(index, &format!("Expected '{}' or '{}' but found '{}'.", lower_half, upper_half, c)));
+        }
+    }
+    Ok(value)
 }
 
 impl Seat {
@@ -76,8 +103,6 @@ mod day5_tests {
     use std::u16;
 
     use super::*;
-    use crate::input;
-    use crate::day;
     use rstest::rstest;
 
     #[rstest]
@@ -105,22 +130,4 @@ mod day5_tests {
     fn test_seat_id(#[case] seat: Seat, #[case] expected_id: u32) {
         assert_eq!(seat.seat_id(), expected_id);
     }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day5{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 5, part: 1}).unwrap();
-        let expected_result = String::from("930");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day5{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 5, part: 2}).unwrap();
-        let expected_result = String::from("515");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
 }
\ No newline at end of file
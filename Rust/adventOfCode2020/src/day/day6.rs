@@ -1,5 +1,7 @@
 use std::collections::{HashSet, HashMap};
 
+use crate::error::PuzzleError;
+
 
 
 
@@ -11,10 +13,19 @@ pub struct TravelGroup{
 
 pub struct Day6 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 6,
+    name: "Custom Customs",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day6 {}),
+};
+
 impl super::Day for Day6{
     type PuzzleInput = Vec<TravelGroup>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
         let lines = text.lines();
         let mut travel_groups: Vec<TravelGroup> = vec![];
         let mut current_customer_forms: Vec<String> = vec![];
@@ -29,23 +40,23 @@ impl super::Day for Day6{
         }
         let final_group = TravelGroup {customer_forms: current_customer_forms};
         travel_groups.push(final_group);
-        travel_groups
+        Ok(travel_groups)
     }
 
-    fn solve_part1(&self, travel_groups: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, travel_groups: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let unique_items_per_group = travel_groups.iter().map(|group| unique_characters(&group.customer_forms));
         let group_items_sum: usize = unique_items_per_group
             .map(|group_items| group_items.len())
             .sum();
-        group_items_sum.to_string()
+        Ok(group_items_sum.to_string())
     }
 
-    fn solve_part2(&self, travel_groups: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, travel_groups: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let common_items_per_group = travel_groups.iter().map(|group| common_characters(&group.customer_forms));
         let group_items_sum: usize = common_items_per_group
             .map(|group_items| group_items.len())
             .sum();
-        group_items_sum.to_string()
+        Ok(group_items_sum.to_string())
     }
 }
 
@@ -86,7 +97,6 @@ fn increment_item_count(item_counts: &mut HashMap<char, usize>, item: char){
 #[cfg(test)]
 mod day6_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -113,7 +123,7 @@ b")
         let day: Box<dyn day::DaySolver> = Box::new(Day6{});
         let problem_input = example_input();
         let expected_result = String::from("11");
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -122,25 +132,7 @@ b")
         let day: Box<dyn day::DaySolver> = Box::new(Day6{});
         let problem_input = example_input();
         let expected_result = String::from("6");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day6{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 6, part: 1}).unwrap();
-        let expected_result = String::from("6763");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day6{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 6, part: 2}).unwrap();
-        let expected_result = String::from("3512");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
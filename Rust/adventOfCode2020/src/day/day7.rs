@@ -1,6 +1,9 @@
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
+use crate::error::{ParseError, PuzzleError};
+use super::{parse_lines, parse_number};
+
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone, Default)]
 struct BagType{
@@ -17,62 +20,75 @@ pub struct LuggageRule{
 
 pub struct Day7 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 7,
+    name: "Handy Haversacks",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day7 {}),
+};
+
 impl super::Day for Day7{
     type PuzzleInput = Vec<LuggageRule>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .filter_map(|line| parsed_luggage_rule(line))
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let rules = parse_lines(&text, parsed_luggage_rule)?;
+        Ok(rules)
     }
 
-    fn solve_part1(&self, rules: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, rules: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let my_bag = BagType {appearance: String::from("shiny"), color: String::from("gold")};
         let graph = contained_graph(&rules);
         let containing_bags = descendants(&graph, &my_bag);
         let result = containing_bags.len();
-        result.to_string()
+        Ok(result.to_string())
     }
 
-    fn solve_part2(&self, rules: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, rules: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let my_bag = BagType {appearance: String::from("shiny"), color: String::from("gold")};
         let graph = containing_graph(&rules);
         let containing_bags = total_contained_bags(&graph, &my_bag);
         let result: usize = containing_bags.values().sum();
-        result.to_string()
+        Ok(result.to_string())
     }
 }
 
-fn parsed_luggage_rule(line: &str) -> Option<LuggageRule>{
+fn parsed_luggage_rule(line: &str) -> Result<LuggageRule, ParseError>{
     lazy_static! {
         static ref LUGGAGE_RULE_RE: regex::Regex = regex::Regex::new(r"(\w+) (\w+) bags contain(( no other bags)|(,? \d+ \w+ \w+ bags?)+)").unwrap();
     }
-    let captures: regex::Captures = LUGGAGE_RULE_RE.captures(line)?;
+    let captures: regex::Captures = LUGGAGE_RULE_RE.captures(line)
+        .ok_or_else(|| ParseError::in_input("Expected a rule of the form '<bag> bags contain <bags>'."))?;
     let containing_bag = BagType{ appearance: captures[1].to_owned(), color: captures[2].to_owned()};
-    let contained_bags = parsed_contained_bags(&captures[3])?;
-    Some(LuggageRule {containing_bag, contained_bags })
+    let contained_bags_match = captures.get(3).unwrap();
+    let contained_bags = parsed_contained_bags(contained_bags_match.as_str(), contained_bags_match.start())?;
+    Ok(LuggageRule {containing_bag, contained_bags })
 }
 
-fn parsed_contained_bags(bags_text: &str) -> Option<Vec<(BagType, usize)>>{
+fn parsed_contained_bags(bags_text: &str, column_index: usize) -> Result<Vec<(BagType, usize)>, ParseError>{
     if bags_text == " no other bags"{
-        return Some(Vec::new());
+        return Ok(Vec::new());
     }
 
-    let contained_bag_definitions = bags_text.split(',');
-    let contained_bag_specifications = contained_bag_definitions
-        .filter_map(|bag_definition| parsed_contained_bag(bag_definition))
-        .collect();
-        Some(contained_bag_specifications)
+    let mut contained_bag_specifications = vec![];
+    let mut bag_column_index = column_index;
+    for bag_definition in bags_text.split(','){
+        contained_bag_specifications.push(parsed_contained_bag(bag_definition, bag_column_index)?);
+        bag_column_index += bag_definition.len() + 1;
+    }
+    Ok(contained_bag_specifications)
 }
 
-fn parsed_contained_bag(bag_text: &str) -> Option<(BagType, usize)>{
+fn parsed_contained_bag(bag_text: &str, column_index: usize) -> Result<(BagType, usize), ParseError>{
     lazy_static! {
         static ref BAG_RE: regex::Regex = regex::Regex::new(r"(\d+) (\w+) (\w+) bags?").unwrap();
     }
-    let captures: regex::Captures = BAG_RE.captures(bag_text)?;
+    let captures: regex::Captures = BAG_RE.captures(bag_text)
+        .ok_or_else(|| ParseError::in_line(column_index, "Expected a bag specification of the form '<count> <bag> bags'."))?;
     let contained_bag_type = BagType {appearance: captures[2].to_owned(), color: captures[3].to_owned()};
-    let number_of_contained_bags = captures[1].parse::<usize>().ok()?;
-    Some((contained_bag_type, number_of_contained_bags))
+    let number_of_contained_bags = parse_number::<usize>(&captures[1], column_index + captures.get(1).unwrap().start())?;
+    Ok((contained_bag_type, number_of_contained_bags))
 }
 
 fn contained_graph(rules: &[LuggageRule]) -> HashMap<BagType, Vec<(BagType, usize)>>{
@@ -158,7 +174,6 @@ fn add_to_bags(contained_bags: &mut HashMap<BagType, usize>, bag_type: &BagType,
 #[cfg(test)]
 mod day7_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -190,7 +205,7 @@ dark violet bags contain no other bags.")
         let day: Box<dyn day::DaySolver> = Box::new(Day7{});
         let problem_input = example_input();
         let expected_result = String::from("4");
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -199,7 +214,7 @@ dark violet bags contain no other bags.")
         let day: Box<dyn day::DaySolver> = Box::new(Day7{});
         let problem_input = example_input();
         let expected_result = String::from("32");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -208,25 +223,7 @@ dark violet bags contain no other bags.")
         let day: Box<dyn day::DaySolver> = Box::new(Day7{});
         let problem_input = example_input_2();
         let expected_result = String::from("126");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day7{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 7, part: 1}).unwrap();
-        let expected_result = String::from("161");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day7{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 7, part: 2}).unwrap();
-        let expected_result = String::from("30899");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 }
\ No newline at end of file
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 use std::mem::replace;
 
+use crate::error::{ParseError, PuzzleError};
+use super::{parse_lines, parse_number};
+
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Instruction{
@@ -11,16 +14,24 @@ pub enum Instruction{
 
 pub struct Day8 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 8,
+    name: "Handheld Halting",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day8 {}),
+};
+
 impl super::Day for Day8{
     type PuzzleInput = Vec<Instruction>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .filter_map(|line| parsed_instruction(line))
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let instructions = parse_lines(&text, parsed_instruction)?;
+        Ok(instructions)
     }
 
-    fn solve_part1(&self, instructions: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, instructions: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let handheld = NonLoopingHandHeld {code: instructions};
         let stopping_accumulator = match handheld.execute(0, 0){
             ExecutionResult::Terminated(acc) => acc,
@@ -28,10 +39,10 @@ impl super::Day for Day8{
             ExecutionResult::LoopDetected(acc) => acc,
             ExecutionResult::Running => unreachable!("Execute cannot exit running!"),
         };
-        stopping_accumulator.to_string()
+        Ok(stopping_accumulator.to_string())
     }
 
-    fn solve_part2(&self, mut instructions: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, mut instructions: Self::PuzzleInput) -> Result<String, PuzzleError> {
         for index in 0..instructions.len(){
             match instructions[index] {
                 Instruction::NoOp(arg) => {
@@ -39,7 +50,7 @@ impl super::Day for Day8{
                     let original = replace(&mut instructions[index], new_instruction);
                     let handheld = NonLoopingHandHeld{ code: instructions.clone()};
                     if let ExecutionResult::Terminated(result) = handheld.execute(0, 0) {
-                        return result.to_string();
+                        return Ok(result.to_string());
                     }
                     instructions[index] = original;
                 },
@@ -48,28 +59,29 @@ impl super::Day for Day8{
                     let original = replace(&mut instructions[index], new_instruction);
                     let handheld = NonLoopingHandHeld{ code: instructions.clone()};
                     if let ExecutionResult::Terminated(result) = handheld.execute(0, 0) {
-                        return result.to_string();
+                        return Ok(result.to_string());
                     }
                     instructions[index] = original;
                 },
                 _ => ()
             }
         }
-        String::from("Corruption not found!")
+        Err(PuzzleError::unsolvable("Corruption not found!"))
     }
 }
 
-fn parsed_instruction(instruction_text: &str) -> Option<Instruction>{
+fn parsed_instruction(instruction_text: &str) -> Result<Instruction, ParseError>{
     lazy_static! {
         static ref INSTRUCTION_RE: regex::Regex = regex::Regex::new(r"(nop|acc|jmp) ((\+|-)\d+)").unwrap();
     }
-    let captures: regex::Captures = INSTRUCTION_RE.captures(instruction_text)?;
-    let argument = captures[2].parse::<isize>().ok()?;
+    let captures: regex::Captures = INSTRUCTION_RE.captures(instruction_text)
+        .ok_or_else(|| ParseError::in_input("Expected an instruction of the form 'nop|acc|jmp +/-<number>'."))?;
+    let argument = parse_number::<isize>(&captures[2], captures.get(2).unwrap().start())?;
     match &captures[1]{
-        "nop" => Some(Instruction::NoOp(argument)),
-        "acc" => Some(Instruction::Accumulate(argument)),
-        "jmp" => Some(Instruction::Jump(argument)),
-        _ => None
+        "nop" => Ok(Instruction::NoOp(argument)),
+        "acc" => Ok(Instruction::Accumulate(argument)),
+        "jmp" => Ok(Instruction::Jump(argument)),
+        _ => unreachable!("The regex only matches known operations."),
     }
 }
 
@@ -143,7 +155,6 @@ impl NonLoopingHandHeld{
 #[cfg(test)]
 mod day8_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -164,7 +175,7 @@ acc +6")
         let day: Box<dyn day::DaySolver> = Box::new(Day8{});
         let problem_input = example_input();
         let expected_result = String::from("5");
-        let actual_result = day.solve_part1(problem_input);
+        let actual_result = day.solve_part1(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
@@ -173,25 +184,26 @@ acc +6")
         let day: Box<dyn day::DaySolver> = Box::new(Day8{});
         let problem_input = example_input();
         let expected_result = String::from("8");
-        let actual_result = day.solve_part2(problem_input);
+        let actual_result = day.solve_part2(problem_input).unwrap();
         assert_eq!(actual_result, expected_result);
     }
 
     #[test]
-    fn correct_part1() {
+    fn invalid_instruction_is_reported_with_its_line() {
         let day: Box<dyn day::DaySolver> = Box::new(Day8{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 8, part: 1}).unwrap();
-        let expected_result = String::from("1317");
+        let problem_input = String::from("nop +0\nacc +1\nadd +4");
         let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
+        match actual_result{
+            Err(PuzzleError::Parse(error)) => assert_eq!(error.line, 3),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
     }
 
     #[test]
-    fn correct_part2() {
+    fn uncorrectable_program_is_unsolvable() {
         let day: Box<dyn day::DaySolver> = Box::new(Day8{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 8, part: 2}).unwrap();
-        let expected_result = String::from("1033");
+        let problem_input = String::from("jmp +0\njmp +0");
         let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
+        assert!(matches!(actual_result, Err(PuzzleError::Unsolvable(_))));
     }
 }
\ No newline at end of file
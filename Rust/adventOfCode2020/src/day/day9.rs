@@ -1,46 +1,53 @@
-
-use super::day1;
+use crate::error::PuzzleError;
+use super::{day1, parse_lines, parse_number};
 
 pub struct Day9 {}
 
+pub static REGISTRATION: super::SolverRegistration = super::SolverRegistration {
+    year: 2020,
+    day: 9,
+    name: "Encoding Error",
+    variant: "v1",
+    is_default: true,
+    create: || Box::new(Day9 {}),
+};
+
 impl super::Day for Day9{
-    type PuzzleInput = Vec<u32>;
+    type PuzzleInput = Vec<u64>;
 
-    fn parse_input(&self, text: std::string::String) -> Self::PuzzleInput {
-        text.lines()
-            .map(|line| line.parse::<u32>())
-            .filter_map(Result::ok)
-            .collect()
+    fn parse_input(&self, text: std::string::String) -> Result<Self::PuzzleInput, PuzzleError> {
+        let numbers = parse_lines(&text, |line| parse_number::<u64>(line, 0))?;
+        Ok(numbers)
     }
 
-    fn solve_part1(&self, numbers: Self::PuzzleInput) -> std::string::String {
+    fn solve_part1(&self, numbers: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let first_item_failing_validation = first_invalid_entry(&numbers, 0, 25);
         match first_item_failing_validation {
-            Some(item) => item.to_string(),
-            None => String::from("No invalid entry found!"),
+            Some(item) => Ok(item.to_string()),
+            None => Err(PuzzleError::unsolvable("No invalid entry found!")),
         }
     }
 
-    fn solve_part2(&self, numbers: Self::PuzzleInput) -> std::string::String {
+    fn solve_part2(&self, numbers: Self::PuzzleInput) -> Result<String, PuzzleError> {
         let maybe_first_item_failing_validation = first_invalid_entry(&numbers, 0, 25);
         if maybe_first_item_failing_validation.is_none(){
-            return String::from("No invalid entry found!");
+            return Err(PuzzleError::unsolvable("No invalid entry found!"));
         }
         let first_item_failing_validation = maybe_first_item_failing_validation.unwrap();
         let maybe_first_range_summing_to_invalid_item = first_range_with_sum(&numbers, &first_item_failing_validation, 0);
         if maybe_first_range_summing_to_invalid_item.is_none(){
-            return format!("No range summing to {} found!", first_item_failing_validation);
+            return Err(PuzzleError::Unsolvable(format!("No range summing to {} found!", first_item_failing_validation)));
         }
         let (lower_index, high_index) = maybe_first_range_summing_to_invalid_item.unwrap();
         let range = &numbers[lower_index..high_index];
         let low_value = range.iter().min().unwrap();
         let high_value = range.iter().max().unwrap();
         let result = low_value + high_value;
-        result.to_string()
+        Ok(result.to_string())
     }
 }
 
-fn first_invalid_entry(numbers: &[u32], start_index: usize, preamble_length: usize) -> Option<u32>{
+fn first_invalid_entry(numbers: &[u64], start_index: usize, preamble_length: usize) -> Option<u64>{
     if preamble_length == 0 {
         return None;
     }
@@ -54,12 +61,12 @@ fn first_invalid_entry(numbers: &[u32], start_index: usize, preamble_length: usi
     None
 }
 
-fn is_valid_entry(comparison_value: &u32, comparison_range: &[u32]) -> bool{
+fn is_valid_entry(comparison_value: &u64, comparison_range: &[u64]) -> bool{
     let summing_pair = day1::find_summing_pair(comparison_range, comparison_value);
     summing_pair.is_some()
 }
 
-fn first_range_with_sum(numbers: &[u32], desired_sum: &u32, start_index: usize) -> Option<(usize, usize)>{
+fn first_range_with_sum(numbers: &[u64], desired_sum: &u64, start_index: usize) -> Option<(usize, usize)>{
     let mut low_index = start_index;
     let mut high_index = start_index + 1;
 
@@ -101,7 +108,6 @@ fn first_range_with_sum(numbers: &[u32], desired_sum: &u32, start_index: usize)
 #[cfg(test)]
 mod day9_tests {
     use super::*;
-    use crate::input;
     use crate::day;
 
     fn example_input() -> String{
@@ -130,9 +136,9 @@ mod day9_tests {
 
     #[test]
     fn example_part1() {
-        let day: Box<dyn day::Day<PuzzleInput = Vec<u32>>> = Box::new(Day9{});
+        let day: Box<dyn day::Day<PuzzleInput = Vec<u64>>> = Box::new(Day9{});
         let problem_input = example_input();
-        let parsed_problem_input = day.parse_input(problem_input);
+        let parsed_problem_input = day.parse_input(problem_input).unwrap();
         let expected_result = 127;
         let actual_result = first_invalid_entry(&parsed_problem_input, 0, 5).unwrap();
         assert_eq!(actual_result, expected_result);
@@ -140,31 +146,13 @@ mod day9_tests {
 
     #[test]
     fn example_part2() {
-        let day: Box<dyn day::Day<PuzzleInput = Vec<u32>>> = Box::new(Day9{});
+        let day: Box<dyn day::Day<PuzzleInput = Vec<u64>>> = Box::new(Day9{});
         let problem_input = example_input();
-        let parsed_problem_input = day.parse_input(problem_input);
-        let (lower_bound, upper_bound) = first_range_with_sum(&parsed_problem_input, &127u32, 0).unwrap();
+        let parsed_problem_input = day.parse_input(problem_input).unwrap();
+        let (lower_bound, upper_bound) = first_range_with_sum(&parsed_problem_input, &127u64, 0).unwrap();
         let expected_upper_bound = 2;
         let expected_lower_bound = 5;
         assert_eq!(lower_bound, expected_upper_bound);
         assert_eq!(upper_bound, expected_lower_bound);
     }
-
-    #[test]
-    fn correct_part1() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day9{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 9, part: 1}).unwrap();
-        let expected_result = String::from("1721308972");
-        let actual_result = day.solve_part1(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
-
-    #[test]
-    fn correct_part2() {
-        let day: Box<dyn day::DaySolver> = Box::new(Day9{});
-        let problem_input = input::puzzle_input(&input::PuzzleConfiguration{day: 9, part: 2}).unwrap();
-        let expected_result = String::from("209694133");
-        let actual_result = day.solve_part2(problem_input);
-        assert_eq!(actual_result, expected_result);
-    }
 }
\ No newline at end of file
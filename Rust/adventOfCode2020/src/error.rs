@@ -0,0 +1,131 @@
+use std::{error::Error, fmt::Display, io};
+
+#[derive(Debug)]
+pub enum PuzzleError{
+    UnknownDay{year: i32, day: i32},
+    UnknownVariant{year: i32, day: i32, variant: String},
+    InvalidPart(i32),
+    Io{source: String, error: io::Error},
+    Parse(ParseError),
+    Unsolvable(String),
+    Configuration(String),
+}
+
+impl PuzzleError{
+    pub fn unsolvable(reason: &str) -> PuzzleError{
+        PuzzleError::Unsolvable(String::from(reason))
+    }
+}
+
+///
+/// IO errors cannot be cloned, so a clone of them keeps only their kind and message.
+///
+impl Clone for PuzzleError{
+    fn clone(&self) -> Self {
+        match self{
+            PuzzleError::UnknownDay{year, day} => PuzzleError::UnknownDay {year: *year, day: *day},
+            PuzzleError::UnknownVariant{year, day, variant} => PuzzleError::UnknownVariant {year: *year, day: *day, variant: variant.clone()},
+            PuzzleError::InvalidPart(part) => PuzzleError::InvalidPart(*part),
+            PuzzleError::Io{source, error} => PuzzleError::Io {source: source.clone(), error: io::Error::new(error.kind(), error.to_string())},
+            PuzzleError::Parse(error) => PuzzleError::Parse(error.clone()),
+            PuzzleError::Unsolvable(reason) => PuzzleError::Unsolvable(reason.clone()),
+            PuzzleError::Configuration(reason) => PuzzleError::Configuration(reason.clone()),
+        }
+    }
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self{
+            PuzzleError::UnknownDay{year, day} => write!(f, "There is no solver for day {} of {}.", day, year),
+            PuzzleError::UnknownVariant{year, day, variant} => write!(f, "There is no variant '{}' of the solver for day {} of {}.", variant, day, year),
+            PuzzleError::InvalidPart(part) => write!(f, "Invalid part {}. The part must be 1 or 2.", part),
+            PuzzleError::Io{source, error} => write!(f, "Could not read the input from {}: {}", source, error),
+            PuzzleError::Parse(error) => write!(f, "{}", error),
+            PuzzleError::Unsolvable(reason) => write!(f, "The puzzle could not be solved: {}", reason),
+            PuzzleError::Configuration(reason) => write!(f, "Invalid input configuration: {}", reason),
+        }
+    }
+}
+
+impl Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self{
+            PuzzleError::Io{error, ..} => Some(error),
+            PuzzleError::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for PuzzleError{
+    fn from(error: ParseError) -> Self {
+        PuzzleError::Parse(error)
+    }
+}
+
+///
+/// Error in the puzzle input.
+///
+/// * `line` - One-based line number in the input; 0 if the error is not tied to a line.
+/// * `column` - One-based column number within the line; 0 if the error is not tied to a column.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError{
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError{
+    ///
+    /// Creates an error at the zero-based line and column indices.
+    ///
+    pub fn at(line_index: usize, column_index: usize, message: &str) -> ParseError{
+        ParseError {line: line_index + 1, column: column_index + 1, message: String::from(message)}
+    }
+
+    ///
+    /// Creates an error at the zero-based column index of a line that is not known yet.
+    /// Use `on_line` to attach the line afterwards.
+    ///
+    pub fn in_line(column_index: usize, message: &str) -> ParseError{
+        ParseError {line: 0, column: column_index + 1, message: String::from(message)}
+    }
+
+    ///
+    /// Creates an error that concerns the input as a whole.
+    ///
+    pub fn in_input(message: &str) -> ParseError{
+        ParseError {line: 0, column: 0, message: String::from(message)}
+    }
+
+    ///
+    /// Moves the error to the zero-based line index, keeping the column.
+    ///
+    pub fn on_line(self, line_index: usize) -> ParseError{
+        ParseError {line: line_index + 1, ..self}
+    }
+
+    ///
+    /// Shifts the error by a number of lines, for errors from parsing a block within the input.
+    ///
+    pub fn offset_lines(self, line_offset: usize) -> ParseError{
+        if self.line == 0{
+            return self;
+        }
+        ParseError {line: self.line + line_offset, ..self}
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column){
+            (0, _) => write!(f, "Invalid input: {}", self.message),
+            (line, 0) => write!(f, "Invalid input in line {}: {}", line, self.message),
+            (line, column) => write!(f, "Invalid input in line {}, column {}: {}", line, column, self.message),
+        }
+    }
+}
+
+impl Error for ParseError {}
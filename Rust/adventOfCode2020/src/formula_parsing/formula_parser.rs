@@ -146,6 +146,7 @@ impl<T: BinaryOpParsingStrategy + BaseOnitializable<Self>> FormulaParser for Rig
 }
 
 
+#[derive(Default)]
 pub struct LeftToRightEvaluationBinaryOpParsingStrategy{
     base_parser: Option<Weak<RefCell<RightToLeftFormulaParser<Self>>>>,
 }
@@ -176,6 +177,7 @@ impl BinaryOpParsingStrategy for LeftToRightEvaluationBinaryOpParsingStrategy{
 }
 
 
+#[derive(Default)]
 pub struct PlusBeforeMultEvaluationBinaryOpParsingStrategy{
     base_parser: Option<Weak<RefCell<RightToLeftFormulaParser<Self>>>>,
 }
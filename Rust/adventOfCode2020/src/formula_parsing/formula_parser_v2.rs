@@ -113,6 +113,7 @@ impl<T: BinaryOpParsingStrategy<T>> FormulaParser for RightToLeftFormulaParser<T
 }
 
 
+#[derive(Default)]
 pub struct LeftToRightEvaluationBinaryOpParsingStrategy {}
 
 impl LeftToRightEvaluationBinaryOpParsingStrategy{
@@ -132,6 +133,7 @@ impl BinaryOpParsingStrategy<Self> for LeftToRightEvaluationBinaryOpParsingStrat
 }
 
 
+#[derive(Default)]
 pub struct PlusBeforeMultEvaluationBinaryOpParsingStrategy{}
 
 impl PlusBeforeMultEvaluationBinaryOpParsingStrategy{
@@ -1,6 +1,17 @@
-use std::collections::HashSet;
-use std::hash::Hash;
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
+mod automaton;
+mod dense;
+mod life_like;
+pub use automaton::{Automaton, AutomatonRules, AutomatonState, AutomatonGeneration, AutomatonGenerations, StateHistogram, TwoStateRules};
+pub use dense::{Backend, DenseGameOfLife};
+pub use life_like::{LifeLikeRule, LifeLikeRules};
+
+///
+/// Rules of an automaton in which items are either active or inactive, see `TwoStateRules` for using them with `Automaton`.
+///
 pub trait GameOfLifeRules {
     type ItemType;
     fn neighbours<'a>(&self, item: &'a Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'a;
@@ -8,95 +19,268 @@ pub trait GameOfLifeRules {
     fn flip_inactive(&self, active_neighbour_count: usize) -> bool;
 }
 
+///
+/// An `Automaton` playing the rules with the states `true` for active and `false` for inactive items,
+/// which adds views on the active items and the detection of cycles.
+///
 pub struct GameOfLife<R: GameOfLifeRules>{
-    rules: R,
+    automaton: Automaton<TwoStateRules<R>>,
+}
+
+///
+/// State of a game between two rounds. It can be cloned to save it and passed to `GameOfLife::play_from` to resume the game.
+/// Only the active items are stored, see `active_items`.
+///
+pub type GameState<T> = AutomatonState<T, bool>;
+
+impl<T: Hash + Eq> GameState<T>{
+    pub fn active_items(&self) -> impl Iterator<Item=&T>{
+        self.states.keys()
+    }
+}
+
+///
+/// The items that flipped their state in a round.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generation<T>{
+    ///
+    /// The number of rounds played including this one.
+    ///
+    pub generation: usize,
+    pub flipped_items: Vec<T>,
+}
+
+///
+/// A state that recurs every `period` rounds, first reached after `first_repeating_generation` rounds.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle{
+    pub first_repeating_generation: usize,
+    pub period: usize,
+}
+
+///
+/// How a game ended within a budget of rounds.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stabilization{
+    ///
+    /// No item changes anymore.
+    ///
+    Stable,
+    ///
+    /// The items keep changing, but the game returned to an earlier state.
+    ///
+    Periodic(Cycle),
+    BudgetExhausted,
 }
 
 impl<R: GameOfLifeRules> GameOfLife<R>
     where R::ItemType: Hash + Eq + Copy{
     pub fn new(game_rules: R) -> GameOfLife<R>{
-        GameOfLife{rules: game_rules}
+        GameOfLife{automaton: Automaton::new(TwoStateRules(game_rules))}
     }
 
-    pub fn active_items_after_playing<'a>(&self, rounds_to_play: usize, initially_active_items: impl Iterator<Item=&'a R::ItemType>) -> impl Iterator<Item=R::ItemType> where R::ItemType:'a {
-        let mut active_items: HashSet<R::ItemType> = initially_active_items
-            .map(|item| item.to_owned())
-            .collect();
-        let mut relevant_items: HashSet<R::ItemType> = self.relevant_items_after_changes(active_items.iter().cloned().collect());
+    ///
+    /// The state before the first round, in which all active items and their neighbours might change.
+    ///
+    pub fn initial_state<'a>(&self, initially_active_items: impl Iterator<Item=&'a R::ItemType>) -> GameState<R::ItemType> where R::ItemType:'a {
+        self.automaton.initial_state(initially_active_items.map(|item| (*item, true)))
+    }
 
-        for _ in 0..rounds_to_play{
-            if relevant_items.is_empty(){
-                break;
-            }
-            relevant_items = self.play_round(&mut active_items, relevant_items);
-        }
-        active_items.into_iter()
+    ///
+    /// Plays the game round by round, starting with the given state. The iteration ends once no item changes anymore.
+    ///
+    pub fn play_from(&self, state: GameState<R::ItemType>) -> Generations<'_, R>{
+        Generations {generations: self.automaton.play_from(state)}
     }
 
+    pub fn active_items_after_playing<'a>(&self, rounds_to_play: usize, initially_active_items: impl Iterator<Item=&'a R::ItemType>) -> impl Iterator<Item=R::ItemType> where R::ItemType:'a {
+        let mut generations = self.play_from(self.initial_state(initially_active_items));
+        generations.by_ref()
+            .take(rounds_to_play)
+            .for_each(drop);
+        generations.into_state().states.into_keys()
+    }
+
+    ///
+    /// Never returns if the game oscillates, see `Generations::stabilize_within` for a bounded variant.
+    ///
     pub fn active_items_after_stabelizing<'a>(&self, initially_active_items: impl Iterator<Item=&'a R::ItemType>, initially_relevant_items: Option<impl Iterator<Item=&'a R::ItemType>>) -> impl Iterator<Item=R::ItemType> where R::ItemType:'a {
-        let mut active_items: HashSet<R::ItemType> = initially_active_items
-            .map(|item| item.to_owned())
-            .collect();
-        let mut relevant_items: HashSet<R::ItemType> = match initially_relevant_items {
-            Some(already_relevant_items) => already_relevant_items
+        let mut state = self.initial_state(initially_active_items);
+        if let Some(already_relevant_items) = initially_relevant_items{
+            state.relevant_items = already_relevant_items
                 .map(|item| item.to_owned())
-                .collect(),
-            None => self.relevant_items_after_changes(active_items.iter().cloned().collect())
-        };
-        loop{
-            if relevant_items.is_empty(){
-                break;
-            }
-            relevant_items = self.play_round(&mut active_items, relevant_items);
+                .collect();
         }
-        active_items.into_iter()
+        let mut generations = self.play_from(state);
+        generations.by_ref().for_each(drop);
+        generations.into_state().states.into_keys()
     }
+}
 
-    //Returns the items relevant for the next round.
-    fn play_round(&self, active_items: &mut HashSet<R::ItemType>, relevant_items:HashSet<R::ItemType>) -> HashSet<R::ItemType>{
-        let changing_items: Vec<R::ItemType> = relevant_items.into_iter()
-            .filter(|item| self.item_changes(item, active_items))
-            .collect();
-        change_item_state(&changing_items, active_items);
-        self.relevant_items_after_changes(changing_items)
+///
+/// Iterator over the rounds of a game, yielding the items flipped in each round.
+/// The state after the last round played is available via `state`.
+///
+pub struct Generations<'a, R: GameOfLifeRules>
+    where R::ItemType: Hash + Eq{
+    generations: AutomatonGenerations<'a, TwoStateRules<R>>,
+}
+
+impl<'a, R: GameOfLifeRules> Generations<'a, R>
+    where R::ItemType: Hash + Eq + Copy{
+    pub fn state(&self) -> &GameState<R::ItemType>{
+        self.generations.state()
     }
 
-    fn item_changes(&self, item: &R::ItemType,  active_items: &HashSet<R::ItemType>) -> bool{
-        let count_of_active_neighbours = self.active_neighbours_count(item, active_items);
-        if active_items.contains(item){
-            self.rules.flip_active(count_of_active_neighbours)
-        } else {
-            self.rules.flip_inactive(count_of_active_neighbours)
-        }
+    pub fn into_state(self) -> GameState<R::ItemType>{
+        self.generations.into_state()
+    }
+
+    ///
+    /// Yields the active items after each round instead of the flipped items.
+    ///
+    pub fn active_sets(mut self) -> impl Iterator<Item=HashSet<R::ItemType>> + 'a{
+        std::iter::from_fn(move || {
+            self.next()?;
+            Some(self.state().active_items().copied().collect())
+        })
     }
 
-    fn relevant_items_after_changes(&self, changed_items: Vec<R::ItemType>) -> HashSet<R::ItemType>{
-        let mut relevant_items: HashSet<R::ItemType> = HashSet::new();
-        for item in changed_items.into_iter(){
-            for neighbour in self.rules.neighbours(&item).into_iter(){
-                relevant_items.insert(neighbour);
+    ///
+    /// Plays at most `max_rounds` rounds until either no item changes anymore or the active items are the same as in an earlier generation.
+    /// The states are compared via a hash of their active items, which is updated with the flipped items each round.
+    /// Afterwards, the state is the one in which this has been detected.
+    ///
+    pub fn stabilize_within(&mut self, max_rounds: usize) -> Stabilization{
+        let mut fingerprint = self.state().active_items()
+            .fold(0, |fingerprint, item| fingerprint ^ item_hash(item));
+        let mut earlier_states = HashMap::new();
+        earlier_states.insert(fingerprint, vec![(self.state().generation, self.state().states.clone())]);
+        for _ in 0..max_rounds{
+            let generation = match self.next(){
+                Some(generation) => generation,
+                None => return Stabilization::Stable,
+            };
+            fingerprint = generation.flipped_items.iter()
+                .fold(fingerprint, |fingerprint, item| fingerprint ^ item_hash(item));
+            let states_with_same_fingerprint = earlier_states.entry(fingerprint).or_default();
+            let repeated_state = states_with_same_fingerprint.iter()
+                .find(|(_, active_items)| *active_items == self.generations.state().states);
+            if let Some((first_repeating_generation, _)) = repeated_state{
+                return Stabilization::Periodic(Cycle {
+                    first_repeating_generation: *first_repeating_generation,
+                    period: generation.generation - first_repeating_generation,
+                });
             }
-            relevant_items.insert(item);
+            states_with_same_fingerprint.push((generation.generation, self.generations.state().states.clone()));
         }
-        relevant_items
+        Stabilization::BudgetExhausted
     }
+}
 
-    fn active_neighbours_count(&self, item: &R::ItemType, active_items: &HashSet<R::ItemType>) -> usize{
-        self.rules
-            .neighbours(item)
-            .into_iter()
-            .filter(|neighbour| active_items.contains(neighbour))
-            .count()
+fn item_hash<T: Hash>(item: &T) -> u64{
+    let mut hasher = DefaultHasher::new();
+    item.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<'a, R: GameOfLifeRules> Iterator for Generations<'a, R>
+    where R::ItemType: Hash + Eq + Copy{
+    type Item = Generation<R::ItemType>;
+
+    fn next(&mut self) -> Option<Self::Item>{
+        let generation = self.generations.next()?;
+        let flipped_items = generation.changes.into_iter()
+            .map(|(item, _)| item)
+            .collect();
+        Some(Generation {generation: generation.generation, flipped_items})
     }
 }
 
-fn change_item_state<T: Hash + Eq + Copy>(items_to_change: &[T], active_items: &mut HashSet<T>){
-    for item in items_to_change{
-        if active_items.contains(item){
-            active_items.remove(item);
-        }else{
-            active_items.insert(item.to_owned());
+
+
+#[cfg(test)]
+mod game_of_life_tests {
+    use super::*;
+    use crate::space::{Neighbourhood, Point};
+
+    fn game() -> GameOfLife<LifeLikeRules<'static, Point<i64, 2>>>{
+        GameOfLife::new(LifeLikeRules::with_neighbourhood(LifeLikeRule::new(&[3], &[2, 3]), Neighbourhood::moore()))
+    }
+
+    fn sorted(items: impl IntoIterator<Item = Point<i64, 2>>) -> Vec<Point<i64, 2>>{
+        let mut items: Vec<Point<i64, 2>> = items.into_iter().collect();
+        items.sort_by_key(|point| (point[1], point[0]));
+        items
+    }
+
+    #[test]
+    fn blinker_flips_four_cells_each_round() {
+        let game = game();
+        let blinker = [Point::new([0, 1]), Point::new([1, 1]), Point::new([2, 1])];
+        let generations: Vec<Generation<Point<i64, 2>>> = game.play_from(game.initial_state(blinker.iter())).take(3).collect();
+        assert_eq!(generations.iter().map(|generation| generation.generation).collect::<Vec<usize>>(), vec![1, 2, 3]);
+        assert_eq!(sorted(generations[0].flipped_items.clone()), vec![Point::new([1, 0]), Point::new([0, 1]), Point::new([2, 1]), Point::new([1, 2])]);
+        let active_sets: Vec<HashSet<Point<i64, 2>>> = game.play_from(game.initial_state(blinker.iter())).active_sets().take(2).collect();
+        assert_eq!(sorted(active_sets[0].iter().copied()), vec![Point::new([1, 0]), Point::new([1, 1]), Point::new([1, 2])]);
+        assert_eq!(sorted(active_sets[1].iter().copied()), sorted(blinker.iter().copied()));
+    }
+
+    #[test]
+    fn still_lifes_end_the_iteration() {
+        let game = game();
+        let block = [Point::new([0, 0]), Point::new([1, 0]), Point::new([0, 1]), Point::new([1, 1])];
+        let mut generations = game.play_from(game.initial_state(block.iter()));
+        assert_eq!(generations.next(), None);
+        assert_eq!(generations.state().generation, 1);
+        assert_eq!(generations.state().active_items().copied().collect::<HashSet<Point<i64, 2>>>(), block.iter().copied().collect());
+    }
+
+    #[test]
+    fn resuming_from_a_saved_state() {
+        let game = game();
+        let glider = [Point::new([1, 0]), Point::new([2, 1]), Point::new([0, 2]), Point::new([1, 2]), Point::new([2, 2])];
+        let mut generations = game.play_from(game.initial_state(glider.iter()));
+        generations.by_ref().take(2).for_each(drop);
+        let saved_state = generations.state().clone();
+        generations.by_ref().take(2).for_each(drop);
+        let uninterrupted_state = generations.into_state();
+        let mut resumed_generations = game.play_from(saved_state);
+        resumed_generations.by_ref().take(2).for_each(drop);
+        assert_eq!(resumed_generations.into_state(), uninterrupted_state);
+        let moved_glider: HashSet<Point<i64, 2>> = glider.iter().map(|point| Point::new([point[0] + 1, point[1] + 1])).collect();
+        assert_eq!(uninterrupted_state.active_items().copied().collect::<HashSet<Point<i64, 2>>>(), moved_glider);
+    }
+
+    #[test]
+    fn stabilization_within_a_budget() {
+        let game = game();
+        let block = [Point::new([0, 0]), Point::new([1, 0]), Point::new([0, 1]), Point::new([1, 1])];
+        assert_eq!(game.play_from(game.initial_state(block.iter())).stabilize_within(10), Stabilization::Stable);
+        let blinker = [Point::new([0, 1]), Point::new([1, 1]), Point::new([2, 1])];
+        let mut generations = game.play_from(game.initial_state(blinker.iter()));
+        assert_eq!(generations.stabilize_within(10), Stabilization::Periodic(Cycle {first_repeating_generation: 0, period: 2}));
+        assert_eq!(generations.state().generation, 2);
+        let glider = [Point::new([1, 0]), Point::new([2, 1]), Point::new([0, 2]), Point::new([1, 2]), Point::new([2, 2])];
+        assert_eq!(game.play_from(game.initial_state(glider.iter())).stabilize_within(10), Stabilization::BudgetExhausted);
+    }
+
+    #[test]
+    fn cycles_starting_after_some_generations() {
+        let game = game();
+        //Evolves into the traffic light, four blinkers with period 2.
+        let t_tetromino = [Point::new([0, 0]), Point::new([1, 0]), Point::new([2, 0]), Point::new([1, 1])];
+        let mut generations = game.play_from(game.initial_state(t_tetromino.iter()));
+        match generations.stabilize_within(100){
+            Stabilization::Periodic(cycle) => {
+                assert_eq!(cycle.period, 2);
+                assert!(cycle.first_repeating_generation > 0);
+                assert_eq!(generations.state().generation, cycle.first_repeating_generation + cycle.period);
+            },
+            stabilization => panic!("Expected a cycle, but got {:?}.", stabilization),
         }
     }
 }
-
@@ -0,0 +1,370 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+use super::GameOfLifeRules;
+
+///
+/// Rules of a cellular automaton in which each item has one of several states.
+/// The next state of an item only depends on its current state and on how many of its neighbours are in each state.
+/// All items without another state are in the default state, which must not change if the item and all its neighbours are in it.
+///
+pub trait AutomatonRules {
+    type ItemType;
+    type StateType;
+    fn neighbours<'a>(&self, item: &'a Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'a;
+    fn next_state(&self, state: &Self::StateType, neighbour_states: &StateHistogram<Self::StateType>) -> Self::StateType;
+}
+
+///
+/// The number of neighbours in each state, counting neighbours in the default state as well.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateHistogram<S>{
+    counts: Vec<(S, usize)>,
+}
+
+impl<S: PartialEq> StateHistogram<S>{
+    pub fn count(&self, state: &S) -> usize{
+        self.counts.iter()
+            .find(|(counted_state, _)| counted_state == state)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    pub fn total(&self) -> usize{
+        self.counts.iter()
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    ///
+    /// The states occurring among the neighbours with their counts, in the order of their first occurrence.
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = (&S, usize)>{
+        self.counts.iter()
+            .map(|(state, count)| (state, *count))
+    }
+}
+
+impl<'a, S: Clone + PartialEq + 'a> FromIterator<&'a S> for StateHistogram<S>{
+    fn from_iter<I: IntoIterator<Item = &'a S>>(states: I) -> Self{
+        let mut counts: Vec<(S, usize)> = vec![];
+        for state in states{
+            match counts.iter_mut().find(|(counted_state, _)| counted_state == state){
+                Some((_, count)) => *count += 1,
+                None => counts.push((state.clone(), 1)),
+            }
+        }
+        StateHistogram {counts}
+    }
+}
+
+pub struct Automaton<R: AutomatonRules>{
+    rules: R,
+    default_state: R::StateType,
+}
+
+///
+/// State of an automaton between two rounds, in which only the items in states other than the default are stored.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutomatonState<T: Hash + Eq, S>{
+    pub generation: usize,
+    pub states: HashMap<T, S>,
+    ///
+    /// The items that might change in the next round. All other items keep their state.
+    ///
+    pub relevant_items: HashSet<T>,
+}
+
+///
+/// The new states of the items that changed in a round.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutomatonGeneration<T, S>{
+    pub generation: usize,
+    pub changes: Vec<(T, S)>,
+}
+
+impl<R: AutomatonRules> Automaton<R>
+    where R::ItemType: Hash + Eq + Copy, R::StateType: Clone + PartialEq + Default{
+    pub fn new(rules: R) -> Automaton<R>{
+        Automaton {rules, default_state: R::StateType::default()}
+    }
+
+    ///
+    /// The state before the first round, in which all items in states other than the default and their neighbours might change.
+    ///
+    pub fn initial_state(&self, initial_states: impl Iterator<Item = (R::ItemType, R::StateType)>) -> AutomatonState<R::ItemType, R::StateType>{
+        let states: HashMap<R::ItemType, R::StateType> = initial_states
+            .filter(|(_, state)| *state != self.default_state)
+            .collect();
+        let relevant_items = self.relevant_items_after_changes(states.keys());
+        AutomatonState {generation: 0, states, relevant_items}
+    }
+
+    ///
+    /// Plays the automaton round by round, starting with the given state. The iteration ends once no item changes anymore.
+    ///
+    pub fn play_from(&self, state: AutomatonState<R::ItemType, R::StateType>) -> AutomatonGenerations<'_, R>{
+        AutomatonGenerations {automaton: self, state}
+    }
+
+    pub fn states_after_playing(&self, rounds_to_play: usize, initial_states: impl Iterator<Item = (R::ItemType, R::StateType)>) -> HashMap<R::ItemType, R::StateType>{
+        let mut generations = self.play_from(self.initial_state(initial_states));
+        generations.by_ref()
+            .take(rounds_to_play)
+            .for_each(drop);
+        generations.into_state().states
+    }
+
+    ///
+    /// Never returns if the automaton oscillates.
+    ///
+    pub fn states_after_stabelizing(&self, initial_states: impl Iterator<Item = (R::ItemType, R::StateType)>) -> HashMap<R::ItemType, R::StateType>{
+        let mut generations = self.play_from(self.initial_state(initial_states));
+        generations.by_ref().for_each(drop);
+        generations.into_state().states
+    }
+
+    ///
+    /// The state of the item, which is the default state if it is not stored.
+    ///
+    pub fn state_of<'a>(&'a self, states: &'a HashMap<R::ItemType, R::StateType>, item: &R::ItemType) -> &'a R::StateType{
+        states.get(item).unwrap_or(&self.default_state)
+    }
+
+    //Returns the changed items with their new states.
+    fn play_round(&self, state: &mut AutomatonState<R::ItemType, R::StateType>) -> Vec<(R::ItemType, R::StateType)>{
+        let changes: Vec<(R::ItemType, R::StateType)> = state.relevant_items.iter()
+            .filter_map(|item| {
+                let current_state = self.state_of(&state.states, item);
+                let next_state = self.rules.next_state(current_state, &self.neighbour_states(item, &state.states));
+                if next_state == *current_state{
+                    None
+                } else {
+                    Some((*item, next_state))
+                }
+            })
+            .collect();
+        for (item, new_state) in changes.iter(){
+            if *new_state == self.default_state{
+                state.states.remove(item);
+            } else {
+                state.states.insert(*item, new_state.clone());
+            }
+        }
+        state.relevant_items = self.relevant_items_after_changes(changes.iter().map(|(item, _)| item));
+        state.generation += 1;
+        changes
+    }
+
+    fn neighbour_states(&self, item: &R::ItemType, states: &HashMap<R::ItemType, R::StateType>) -> StateHistogram<R::StateType>{
+        self.rules
+            .neighbours(item)
+            .iter()
+            .map(|neighbour| self.state_of(states, neighbour))
+            .collect()
+    }
+
+    fn relevant_items_after_changes<'a>(&self, changed_items: impl Iterator<Item = &'a R::ItemType>) -> HashSet<R::ItemType> where R::ItemType: 'a{
+        let mut relevant_items = HashSet::new();
+        for item in changed_items{
+            relevant_items.extend(self.rules.neighbours(item));
+            relevant_items.insert(*item);
+        }
+        relevant_items
+    }
+}
+
+///
+/// Iterator over the rounds of an automaton, yielding the changes in each round.
+/// The state after the last round played is available via `state`.
+///
+pub struct AutomatonGenerations<'a, R: AutomatonRules>
+    where R::ItemType: Hash + Eq{
+    automaton: &'a Automaton<R>,
+    state: AutomatonState<R::ItemType, R::StateType>,
+}
+
+impl<'a, R: AutomatonRules> AutomatonGenerations<'a, R>
+    where R::ItemType: Hash + Eq{
+    pub fn state(&self) -> &AutomatonState<R::ItemType, R::StateType>{
+        &self.state
+    }
+
+    pub fn into_state(self) -> AutomatonState<R::ItemType, R::StateType>{
+        self.state
+    }
+}
+
+impl<'a, R: AutomatonRules> Iterator for AutomatonGenerations<'a, R>
+    where R::ItemType: Hash + Eq + Copy, R::StateType: Clone + PartialEq + Default{
+    type Item = AutomatonGeneration<R::ItemType, R::StateType>;
+
+    fn next(&mut self) -> Option<Self::Item>{
+        if self.state.relevant_items.is_empty(){
+            return None;
+        }
+        let changes = self.automaton.play_round(&mut self.state);
+        if changes.is_empty(){
+            return None;
+        }
+        Some(AutomatonGeneration {generation: self.state.generation, changes})
+    }
+}
+
+///
+/// The rules of a game of life as an automaton with the states `true` for active and `false` for inactive items.
+/// `GameOfLife` plays them on an `Automaton`, in which only the active items are stored.
+///
+pub struct TwoStateRules<R>(pub R);
+
+impl<R: GameOfLifeRules> AutomatonRules for TwoStateRules<R>{
+    type ItemType = R::ItemType;
+    type StateType = bool;
+
+    fn neighbours<'a>(&self, item: &'a Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'a {
+        self.0.neighbours(item)
+    }
+
+    fn next_state(&self, is_active: &bool, neighbour_states: &StateHistogram<bool>) -> bool {
+        let active_neighbour_count = neighbour_states.count(&true);
+        if *is_active{
+            !self.0.flip_active(active_neighbour_count)
+        } else {
+            self.0.flip_inactive(active_neighbour_count)
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod automaton_tests {
+    use super::*;
+    use crate::game_of_life::{LifeLikeRule, LifeLikeRules};
+    use crate::space::{Neighbourhood, Point};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    enum BrainCell{
+        #[default]
+        Off,
+        On,
+        Dying,
+    }
+
+    struct BriansBrain {
+        neighbourhood: Neighbourhood<i64, 2>,
+    }
+
+    impl AutomatonRules for BriansBrain{
+        type ItemType = Point<i64, 2>;
+        type StateType = BrainCell;
+
+        fn neighbours<'a>(&self, item: &'a Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'a {
+            self.neighbourhood.neighbours(item).collect()
+        }
+
+        fn next_state(&self, state: &BrainCell, neighbour_states: &StateHistogram<BrainCell>) -> BrainCell {
+            match state{
+                BrainCell::Off if neighbour_states.count(&BrainCell::On) == 2 => BrainCell::On,
+                BrainCell::Off => BrainCell::Off,
+                BrainCell::On => BrainCell::Dying,
+                BrainCell::Dying => BrainCell::Off,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    enum Wire{
+        #[default]
+        Empty,
+        ElectronHead,
+        ElectronTail,
+        Conductor,
+    }
+
+    struct Wireworld {
+        neighbourhood: Neighbourhood<i64, 2>,
+    }
+
+    impl AutomatonRules for Wireworld{
+        type ItemType = Point<i64, 2>;
+        type StateType = Wire;
+
+        fn neighbours<'a>(&self, item: &'a Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'a {
+            self.neighbourhood.neighbours(item).collect()
+        }
+
+        fn next_state(&self, state: &Wire, neighbour_states: &StateHistogram<Wire>) -> Wire {
+            match state{
+                Wire::Empty => Wire::Empty,
+                Wire::ElectronHead => Wire::ElectronTail,
+                Wire::ElectronTail => Wire::Conductor,
+                Wire::Conductor => match neighbour_states.count(&Wire::ElectronHead){
+                    1 | 2 => Wire::ElectronHead,
+                    _ => Wire::Conductor,
+                },
+            }
+        }
+    }
+
+    fn sorted<S>(states: HashMap<Point<i64, 2>, S>) -> Vec<(Point<i64, 2>, S)>{
+        let mut states: Vec<(Point<i64, 2>, S)> = states.into_iter().collect();
+        states.sort_by_key(|(point, _)| (point[1], point[0]));
+        states
+    }
+
+    #[test]
+    fn histograms() {
+        let histogram: StateHistogram<Wire> = [Wire::Conductor, Wire::Empty, Wire::Conductor].iter().collect();
+        assert_eq!(histogram.count(&Wire::Conductor), 2);
+        assert_eq!(histogram.count(&Wire::ElectronHead), 0);
+        assert_eq!(histogram.total(), 3);
+        assert_eq!(histogram.iter().collect::<Vec<(&Wire, usize)>>(), vec![(&Wire::Conductor, 2), (&Wire::Empty, 1)]);
+    }
+
+    #[test]
+    fn brians_brain_spawns_two_gliders() {
+        let automaton = Automaton::new(BriansBrain {neighbourhood: Neighbourhood::moore()});
+        let initial_states = vec![(Point::new([0, 0]), BrainCell::On), (Point::new([1, 0]), BrainCell::On)];
+        let states = automaton.states_after_playing(1, initial_states.into_iter());
+        assert_eq!(sorted(states), vec![
+            (Point::new([0, -1]), BrainCell::On),
+            (Point::new([1, -1]), BrainCell::On),
+            (Point::new([0, 0]), BrainCell::Dying),
+            (Point::new([1, 0]), BrainCell::Dying),
+            (Point::new([0, 1]), BrainCell::On),
+            (Point::new([1, 1]), BrainCell::On),
+        ]);
+    }
+
+    #[test]
+    fn electrons_move_along_a_wireworld_wire() {
+        let automaton = Automaton::new(Wireworld {neighbourhood: Neighbourhood::moore()});
+        let wire = [Wire::ElectronTail, Wire::ElectronHead, Wire::Conductor, Wire::Conductor, Wire::Conductor];
+        let initial_states = wire.iter().enumerate().map(|(x, state)| (Point::new([x as i64, 0]), *state));
+        let mut generations = automaton.play_from(automaton.initial_state(initial_states));
+        let first_generation = generations.next().unwrap();
+        assert_eq!(first_generation.generation, 1);
+        assert_eq!(first_generation.changes.len(), 3);
+        let states = generations.into_state().states;
+        assert_eq!(sorted(states).into_iter().map(|(_, state)| state).collect::<Vec<Wire>>(),
+            vec![Wire::Conductor, Wire::ElectronTail, Wire::ElectronHead, Wire::Conductor, Wire::Conductor]);
+        let electron_at_the_end = [Wire::Conductor, Wire::Conductor, Wire::Conductor, Wire::ElectronTail, Wire::ElectronHead];
+        let initial_states = electron_at_the_end.iter().enumerate().map(|(x, state)| (Point::new([x as i64, 0]), *state));
+        let states = automaton.states_after_stabelizing(initial_states);
+        assert!(states.values().all(|state| *state == Wire::Conductor));
+    }
+
+    #[test]
+    fn game_of_life_is_a_two_state_automaton() {
+        let glider = [Point::new([1, 0]), Point::new([2, 1]), Point::new([0, 2]), Point::new([1, 2]), Point::new([2, 2])];
+        let automaton = Automaton::new(TwoStateRules(LifeLikeRules::with_neighbourhood(LifeLikeRule::new(&[3], &[2, 3]), Neighbourhood::moore())));
+        let states = automaton.states_after_playing(8, glider.iter().map(|point| (*point, true)));
+        assert!(states.values().all(|is_active| *is_active));
+        let moved_glider: HashSet<Point<i64, 2>> = glider.iter().map(|point| Point::new([point[0] + 2, point[1] + 2])).collect();
+        assert_eq!(states.keys().copied().collect::<HashSet<Point<i64, 2>>>(), moved_glider);
+    }
+}
@@ -0,0 +1,277 @@
+use std::hash::Hash;
+
+use super::{GameOfLife, GameOfLifeRules};
+use crate::space::{BoundingBox, Point};
+
+const WORD_BITS: usize = 64;
+
+///
+/// Selects how a game of life on points is played, see `GameOfLife` and `DenseGameOfLife`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend{
+    Hashed,
+    Dense,
+}
+
+impl Backend{
+    ///
+    /// Returns `None` if the backend cannot play the rules, see `DenseGameOfLife::new`.
+    ///
+    pub fn active_points_after_playing<'a, R, T, const N: usize>(self, rules: R, rounds_to_play: usize, initially_active_points: impl Iterator<Item = &'a Point<T, N>>) -> Option<Vec<Point<T, N>>>
+            where R: GameOfLifeRules<ItemType = Point<T, N>>, T: num::PrimInt + num::Signed + Default + Hash + 'a{
+        match self{
+            Backend::Hashed => Some(GameOfLife::new(rules).active_items_after_playing(rounds_to_play, initially_active_points).collect()),
+            Backend::Dense => Some(DenseGameOfLife::new(&rules)?.active_items_after_playing(rounds_to_play, initially_active_points).collect()),
+        }
+    }
+}
+
+///
+/// Plays a game of life on a bit array covering the box around the active points, which grows and shrinks with them.
+/// In each round, the neighbour counts of all points in the box are accumulated from the active points at once,
+/// using that each neighbour is at a fixed offset in the array.
+///
+/// The neighbours given by the rules must be the same offsets from every point, the negation of each offset must be an offset as well,
+/// and inactive points without active neighbours must stay inactive. Under these conditions, the results are identical
+/// to those of `GameOfLife` with the same rules, which only reconsiders the neighbours of the items that changed.
+///
+pub struct DenseGameOfLife<T, const N: usize>{
+    offsets: Vec<[isize; N]>,
+    reach: T,
+    //Indexed by the number of active neighbours.
+    survives: Vec<bool>,
+    is_born: Vec<bool>,
+}
+
+impl<T: num::PrimInt + num::Signed + Default, const N: usize> DenseGameOfLife<T, N>{
+    ///
+    /// The offsets of the neighbours are taken from the neighbours of the origin.
+    /// Returns `None` if points without active neighbours become active, which would need an unbounded board,
+    /// or if some point is a neighbour of another point without the converse being true.
+    ///
+    pub fn new<R: GameOfLifeRules<ItemType = Point<T, N>>>(rules: &R) -> Option<DenseGameOfLife<T, N>>{
+        let origin = Point::new([T::zero(); N]);
+        let neighbours = rules.neighbours(&origin);
+        let mut offsets = vec![];
+        let mut reach = T::zero();
+        for neighbour in neighbours.iter(){
+            let mut offset = [0; N];
+            for (axis, coordinate) in offset.iter_mut().enumerate(){
+                *coordinate = neighbour[axis].to_isize().unwrap();
+                reach = reach.max(neighbour[axis].abs());
+            }
+            offsets.push(offset);
+        }
+        let survives: Vec<bool> = (0..=offsets.len())
+            .map(|count| !rules.flip_active(count))
+            .collect();
+        let is_born: Vec<bool> = (0..=offsets.len())
+            .map(|count| rules.flip_inactive(count))
+            .collect();
+        let is_symmetric = offsets.iter()
+            .all(|offset| offsets.contains(&offset.map(|coordinate| -coordinate)));
+        if is_born[0] || !is_symmetric{
+            return None;
+        }
+        Some(DenseGameOfLife {offsets, reach, survives, is_born})
+    }
+
+    pub fn active_items_after_playing<'a>(&self, rounds_to_play: usize, initially_active_points: impl Iterator<Item = &'a Point<T, N>>) -> impl Iterator<Item = Point<T, N>> where T: 'a{
+        let initially_active_points: Vec<Point<T, N>> = initially_active_points.copied().collect();
+        let bounding_box = match BoundingBox::from_points(initially_active_points.iter().copied()){
+            Some(bounding_box) => bounding_box,
+            None => return vec![].into_iter(),
+        };
+        let mut board = BitBoard::new(bounding_box);
+        for point in initially_active_points.iter(){
+            board.set(board.index(point));
+        }
+        for _ in 0..rounds_to_play{
+            board = match self.play_round(&board){
+                Some(next_board) => next_board,
+                None => return vec![].into_iter(),
+            };
+        }
+        let active_points: Vec<Point<T, N>> = board.active_indices()
+            .map(|index| board.point(index))
+            .collect();
+        active_points.into_iter()
+    }
+
+    //Returns None if no point is active afterwards.
+    fn play_round(&self, board: &BitBoard<T, N>) -> Option<BitBoard<T, N>>{
+        let mut next_board = BitBoard::new(board.bounding_box.expand(self.reach));
+        let deltas: Vec<isize> = self.offsets.iter()
+            .map(|offset| (0..N).map(|axis| offset[axis] * next_board.strides[axis] as isize).sum())
+            .collect();
+        let mut neighbour_counts = vec![0usize; next_board.volume];
+        let mut was_active = vec![false; next_board.volume];
+        for index in board.active_indices(){
+            let next_index = next_board.index(&board.point(index));
+            was_active[next_index] = true;
+            //The point is the neighbour at the offset of the point at its negation.
+            for delta in deltas.iter(){
+                neighbour_counts[(next_index as isize - delta) as usize] += 1;
+            }
+        }
+        for (index, (count, is_active)) in neighbour_counts.into_iter().zip(was_active).enumerate(){
+            let stays_active = if is_active { self.survives[count] } else { self.is_born[count] };
+            if stays_active{
+                next_board.set(index);
+            }
+        }
+        next_board.shrunk()
+    }
+}
+
+///
+/// Active points as bits in row-major order within a box, i.e. with the first coordinate changing fastest.
+///
+struct BitBoard<T, const N: usize>{
+    bounding_box: BoundingBox<T, N>,
+    strides: [usize; N],
+    volume: usize,
+    words: Vec<u64>,
+}
+
+impl<T: num::PrimInt + Default, const N: usize> BitBoard<T, N>{
+    fn new(bounding_box: BoundingBox<T, N>) -> BitBoard<T, N>{
+        let mut strides = [0; N];
+        let mut volume = 1;
+        for (stride, extent) in strides.iter_mut().zip(bounding_box.size().iter()){
+            *stride = volume;
+            volume *= extent.to_usize().unwrap();
+        }
+        let words = vec![0; volume.div_ceil(WORD_BITS)];
+        BitBoard {bounding_box, strides, volume, words}
+    }
+
+    fn index(&self, point: &Point<T, N>) -> usize{
+        let lower_bound = self.bounding_box.lower_bound();
+        (0..N)
+            .map(|axis| (point[axis] - lower_bound[axis]).to_usize().unwrap() * self.strides[axis])
+            .sum()
+    }
+
+    fn point(&self, index: usize) -> Point<T, N>{
+        let lower_bound = self.bounding_box.lower_bound();
+        let mut arr = [T::zero(); N];
+        let mut remaining_index = index;
+        for axis in (0..N).rev(){
+            arr[axis] = lower_bound[axis] + T::from(remaining_index / self.strides[axis]).unwrap();
+            remaining_index %= self.strides[axis];
+        }
+        Point::new(arr)
+    }
+
+    fn set(&mut self, index: usize){
+        self.words[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+    }
+
+    fn active_indices(&self) -> impl Iterator<Item = usize> + '_{
+        self.words.iter()
+            .enumerate()
+            .flat_map(|(word_index, word)| {
+                let mut remaining_bits = *word;
+                std::iter::from_fn(move || {
+                    if remaining_bits == 0{
+                        return None;
+                    }
+                    let bit = remaining_bits.trailing_zeros() as usize;
+                    remaining_bits &= remaining_bits - 1;
+                    Some(word_index * WORD_BITS + bit)
+                })
+            })
+    }
+
+    ///
+    /// The board restricted to the smallest box around the active points, or `None` if there are none.
+    ///
+    fn shrunk(self) -> Option<BitBoard<T, N>>{
+        let bounding_box = BoundingBox::from_points(self.active_indices().map(|index| self.point(index)))?;
+        if bounding_box == self.bounding_box{
+            return Some(self);
+        }
+        let mut board = BitBoard::new(bounding_box);
+        for index in self.active_indices(){
+            board.set(board.index(&self.point(index)));
+        }
+        Some(board)
+    }
+}
+
+
+
+#[cfg(test)]
+mod dense_tests {
+    use super::*;
+    use crate::game_of_life::{LifeLikeRule, LifeLikeRules};
+    use crate::space::{Neighbourhood, Vector};
+    use std::collections::HashSet;
+    use rstest::rstest;
+
+    fn assert_same_results<const N: usize>(rule: &str, neighbourhood: Neighbourhood<i64, N>, initially_active_points: &[Point<i64, N>], rounds_to_play: usize){
+        let rules = LifeLikeRules::with_neighbourhood(LifeLikeRule::parse(rule).unwrap(), neighbourhood.clone());
+        let dense_points: Vec<Point<i64, N>> = DenseGameOfLife::new(&rules).unwrap().active_items_after_playing(rounds_to_play, initially_active_points.iter()).collect();
+        let hashed_points: HashSet<Point<i64, N>> = GameOfLife::new(rules).active_items_after_playing(rounds_to_play, initially_active_points.iter()).collect();
+        assert_eq!(dense_points.len(), hashed_points.len());
+        assert_eq!(dense_points.into_iter().collect::<HashSet<Point<i64, N>>>(), hashed_points);
+    }
+
+    fn r_pentomino() -> Vec<Point<i64, 2>>{
+        vec![Point::new([1, 0]), Point::new([2, 0]), Point::new([0, 1]), Point::new([1, 1]), Point::new([1, 2])]
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(50)]
+    fn conways_life(#[case] rounds_to_play: usize) {
+        assert_same_results("B3/S23", Neighbourhood::moore(), &r_pentomino(), rounds_to_play);
+    }
+
+    #[test]
+    fn other_neighbourhoods() {
+        assert_same_results("B2/S12", Neighbourhood::<i64, 2>::hex(), &r_pentomino(), 20);
+        assert_same_results("B1/S1", Neighbourhood::von_neumann(), &r_pentomino(), 10);
+        assert_same_results("B3/S2", Neighbourhood::moore_with_radius(2), &r_pentomino(), 10);
+        let cube = [Point::new([0, 0, 0]), Point::new([1, 0, 0]), Point::new([0, 1, 0]), Point::new([0, 0, -1])];
+        assert_same_results("B3/S23", Neighbourhood::moore(), &cube, 6);
+        let knight_moves = Neighbourhood::custom(vec![Vector::new([1, 2]), Vector::new([-1, -2]), Vector::new([2, -1]), Vector::new([-2, 1])]);
+        assert_same_results("B1/S12", knight_moves, &r_pentomino(), 10);
+    }
+
+    #[test]
+    fn asymmetric_neighbourhoods_are_rejected() {
+        let rules = LifeLikeRules::with_neighbourhood(LifeLikeRule::parse("B1/S1").unwrap(), Neighbourhood::custom(vec![Vector::new([1, 0])]));
+        assert!(DenseGameOfLife::new(&rules).is_none());
+        assert_eq!(Backend::Dense.active_points_after_playing(rules, 1, r_pentomino().iter()), None);
+    }
+
+    #[test]
+    fn dying_out() {
+        let rules = LifeLikeRules::with_neighbourhood(LifeLikeRule::parse("B3/S23").unwrap(), Neighbourhood::moore());
+        let pair = [Point::new([0, 0]), Point::new([1, 0])];
+        assert_eq!(DenseGameOfLife::new(&rules).unwrap().active_items_after_playing(3, pair.iter()).count(), 0);
+    }
+
+    #[test]
+    fn births_without_neighbours_are_rejected() {
+        let rules = LifeLikeRules::with_neighbourhood(LifeLikeRule::parse("B0/S").unwrap(), Neighbourhood::<i64, 2>::moore());
+        assert!(DenseGameOfLife::new(&rules).is_none());
+        let single_point = [Point::new([0, 0])];
+        assert_eq!(Backend::Dense.active_points_after_playing(rules, 1, single_point.iter()), None);
+    }
+
+    #[rstest]
+    #[case(Backend::Hashed)]
+    #[case(Backend::Dense)]
+    fn selecting_the_backend(#[case] backend: Backend) {
+        let rules = LifeLikeRules::with_neighbourhood(LifeLikeRule::parse("B3/S23").unwrap(), Neighbourhood::moore());
+        let blinker = [Point::new([0, 1]), Point::new([1, 1]), Point::new([2, 1])];
+        let mut active_points = backend.active_points_after_playing(rules, 1, blinker.iter()).unwrap();
+        active_points.sort_by_key(|point| point[1]);
+        assert_eq!(active_points, vec![Point::new([1, 0]), Point::new([1, 1]), Point::new([1, 2])]);
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::{self, Display};
+
+use super::GameOfLifeRules;
+use crate::error::ParseError;
+use crate::space::{Neighbourhood, Point};
+
+///
+/// Birth and survival conditions of a life-like game, in the notation `B3/S23`:
+/// an inactive item becomes active with 3 active neighbours and an active item stays active with 2 or 3.
+/// Each count is a single digit, so only counts up to 9 can be used.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LifeLikeRule{
+    birth_counts: Vec<usize>,
+    survival_counts: Vec<usize>,
+}
+
+impl LifeLikeRule{
+    pub fn new(birth_counts: &[usize], survival_counts: &[usize]) -> LifeLikeRule{
+        LifeLikeRule {birth_counts: sorted_counts(birth_counts), survival_counts: sorted_counts(survival_counts)}
+    }
+
+    ///
+    /// Parses rules like `B3/S23` or `b2/s12`, in which either part may come first and may list no counts.
+    ///
+    pub fn parse(text: &str) -> Result<LifeLikeRule, ParseError>{
+        let mut birth_counts = None;
+        let mut survival_counts = None;
+        let mut column_index = 0;
+        for part in text.split('/'){
+            let counts = match part.chars().next(){
+                Some('B') | Some('b') if birth_counts.is_none() => &mut birth_counts,
+                Some('S') | Some('s') if survival_counts.is_none() => &mut survival_counts,
+                _ => return Err(ParseError::in_line(column_index, "Expected the birth counts starting with 'B' or the survival counts starting with 'S'.")),
+            };
+            let mut part_counts = vec![];
+            for (index, character) in part.char_indices().skip(1){
+                let count = character.to_digit(10)
+                    .ok_or_else(|| ParseError::in_line(column_index + index, &format!("'{}' is not a neighbour count.", character)))?;
+                part_counts.push(count as usize);
+            }
+            *counts = Some(part_counts);
+            column_index += part.len() + 1;
+        }
+        match (birth_counts, survival_counts){
+            (Some(birth_counts), Some(survival_counts)) => Ok(LifeLikeRule::new(&birth_counts, &survival_counts)),
+            _ => Err(ParseError::in_line(text.len(), "Expected both birth and survival counts, e.g. 'B3/S23'.")),
+        }
+    }
+
+    pub fn is_born(&self, active_neighbour_count: usize) -> bool{
+        self.birth_counts.contains(&active_neighbour_count)
+    }
+
+    pub fn survives(&self, active_neighbour_count: usize) -> bool{
+        self.survival_counts.contains(&active_neighbour_count)
+    }
+}
+
+fn sorted_counts(counts: &[usize]) -> Vec<usize>{
+    let mut counts = counts.to_vec();
+    counts.sort_unstable();
+    counts.dedup();
+    counts
+}
+
+impl Display for LifeLikeRule{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        let digits = |counts: &[usize]| -> String {
+            counts.iter()
+                .map(|count| count.to_string())
+                .collect()
+        };
+        write!(f, "B{}/S{}", digits(&self.birth_counts), digits(&self.survival_counts))
+    }
+}
+
+type NeighbourFunction<'a, T> = Box<dyn Fn(&T) -> Vec<T> + 'a>;
+
+///
+/// Game of life rules given by a `LifeLikeRule` and the neighbours of each item,
+/// e.g. those of a `Neighbourhood` or the first seats in sight on a grid.
+///
+pub struct LifeLikeRules<'a, T>{
+    rule: LifeLikeRule,
+    neighbours: NeighbourFunction<'a, T>,
+}
+
+impl<'a, T> LifeLikeRules<'a, T>{
+    pub fn new(rule: LifeLikeRule, neighbours: impl Fn(&T) -> Vec<T> + 'a) -> LifeLikeRules<'a, T>{
+        LifeLikeRules {rule, neighbours: Box::new(neighbours)}
+    }
+
+    pub fn rule(&self) -> &LifeLikeRule{
+        &self.rule
+    }
+}
+
+impl<'a, C: num::PrimInt + num::Signed + Default + 'a, const N: usize> LifeLikeRules<'a, Point<C, N>>{
+    ///
+    /// Rules in which the neighbours are the points at the offsets of the neighbourhood, regardless of its reach.
+    ///
+    pub fn with_neighbourhood(rule: LifeLikeRule, neighbourhood: Neighbourhood<C, N>) -> LifeLikeRules<'a, Point<C, N>>{
+        LifeLikeRules::new(rule, move |point| neighbourhood.neighbours(point).collect())
+    }
+}
+
+impl<'a, T> GameOfLifeRules for LifeLikeRules<'a, T>{
+    type ItemType = T;
+
+    fn neighbours<'b>(&self, item: &'b Self::ItemType) -> Vec<Self::ItemType> where Self::ItemType: 'b {
+        (self.neighbours)(item)
+    }
+
+    fn flip_active(&self, active_neighbour_count: usize) -> bool {
+        !self.rule.survives(active_neighbour_count)
+    }
+
+    fn flip_inactive(&self, active_neighbour_count: usize) -> bool {
+        self.rule.is_born(active_neighbour_count)
+    }
+}
+
+
+
+#[cfg(test)]
+mod life_like_tests {
+    use super::*;
+    use crate::game_of_life::GameOfLife;
+    use crate::space::Axial;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("B3/S23", "B3/S23")]
+    #[case("s32/b3", "B3/S23")]
+    #[case("B0/S", "B0/S")]
+    #[case("B36/S2233", "B36/S23")]
+    fn parsing_and_display(#[case] text: &str, #[case] expected_text: &str) {
+        assert_eq!(LifeLikeRule::parse(text).unwrap().to_string(), expected_text);
+    }
+
+    #[rstest]
+    #[case("", 1)]
+    #[case("B3", 3)]
+    #[case("B3S23", 3)]
+    #[case("B3/X23", 4)]
+    #[case("B3/B2", 4)]
+    #[case("B3/S2a", 6)]
+    fn invalid_rules(#[case] text: &str, #[case] expected_column: usize) {
+        let error = LifeLikeRule::parse(text).unwrap_err();
+        assert_eq!(error.column, expected_column);
+    }
+
+    #[test]
+    fn conditions() {
+        let rule = LifeLikeRule::parse("B2/S12").unwrap();
+        assert!(rule.is_born(2) && !rule.is_born(1));
+        assert!(rule.survives(1) && rule.survives(2) && !rule.survives(0) && !rule.survives(3));
+    }
+
+    #[test]
+    fn conways_life_in_three_dimensions() {
+        let rules = LifeLikeRules::with_neighbourhood(LifeLikeRule::parse("B3/S23").unwrap(), Neighbourhood::<i64, 3>::moore());
+        let blinker = [Point::new([0, 1, 0]), Point::new([1, 1, 0]), Point::new([2, 1, 0])];
+        let active_points: Vec<Point<i64, 3>> = GameOfLife::new(rules).active_items_after_playing(1, blinker.iter()).collect();
+        assert_eq!(active_points.len(), 9);
+    }
+
+    #[test]
+    fn rules_on_hexagonal_tiles() {
+        let rules = LifeLikeRules::new(LifeLikeRule::parse("B2/S12").unwrap(), |tile: &Axial| tile.neighbours().to_vec());
+        let tiles = [Axial::new(0, 0), Axial::new(1, 0)];
+        let mut active_tiles: Vec<Axial> = GameOfLife::new(rules).active_items_after_playing(1, tiles.iter()).collect();
+        active_tiles.sort_by_key(|tile| (tile.q, tile.r));
+        assert_eq!(active_tiles, vec![Axial::new(0, 0), Axial::new(0, 1), Axial::new(1, -1), Axial::new(1, 0)]);
+    }
+}
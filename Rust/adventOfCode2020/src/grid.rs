@@ -3,7 +3,19 @@ use std::hash::Hash;
 use std::ops::{Add, Sub, Rem};
 
 use crate::util;
-use crate::space::Point;
+use crate::space::{BoundingBox, Point, RowMajorPoints, SignedPermutation};
+
+mod combinators;
+mod dense;
+mod infinite;
+mod text;
+mod view;
+
+pub use combinators::GridCombinators;
+pub use dense::{DenseGrid, OutsideBehaviour};
+pub use infinite::InfiniteGrid;
+pub use text::TextCodec;
+pub use view::{Cells, CroppedView, TransformedView};
 
 #[derive(Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Default)]
 pub struct CoordinateRange<T>{
@@ -11,6 +23,29 @@ pub struct CoordinateRange<T>{
     pub upper_bound: T,
 }
 
+impl<T: Default + Copy, const N: usize> From<[CoordinateRange<T>; N]> for BoundingBox<T, N>{
+    fn from(coordinate_ranges: [CoordinateRange<T>; N]) -> Self{
+        let mut lower_bound = [T::default(); N];
+        let mut upper_bound = [T::default(); N];
+        for (axis, range) in coordinate_ranges.iter().enumerate(){
+            lower_bound[axis] = range.lower_bound;
+            upper_bound[axis] = range.upper_bound;
+        }
+        BoundingBox::new(Point::new(lower_bound), Point::new(upper_bound))
+    }
+}
+
+impl<T: Default + Copy, const N: usize> From<BoundingBox<T, N>> for [CoordinateRange<T>; N]{
+    fn from(bounding_box: BoundingBox<T, N>) -> Self{
+        let mut coordinate_ranges = [CoordinateRange::default(); N];
+        for (axis, range) in coordinate_ranges.iter_mut().enumerate(){
+            range.lower_bound = bounding_box.lower_bound()[axis];
+            range.upper_bound = bounding_box.upper_bound()[axis];
+        }
+        coordinate_ranges
+    }
+}
+
 pub trait Grid<V, const N: usize>{
     type CoordinateType;
     fn coordinate_ranges(&self) -> &[CoordinateRange<Self::CoordinateType>; N];
@@ -20,6 +55,78 @@ pub trait Grid<V, const N: usize>{
         let coordinate_ranges = self.coordinate_ranges();
         is_on_main_grid(point, coordinate_ranges)
     }
+
+    ///
+    /// Whether points outside the coordinate ranges have a default value or repeat the grid.
+    ///
+    fn outside_behaviour(&self) -> OutsideBehaviour{
+        OutsideBehaviour::Default
+    }
+
+    fn bounding_box(&self) -> BoundingBox<Self::CoordinateType, N>
+            where <Self as Grid<V, N>>::CoordinateType: Default + Copy{
+        BoundingBox::from(*self.coordinate_ranges())
+    }
+
+    ///
+    /// The points within the coordinate ranges in row-major order.
+    ///
+    fn points(&self) -> RowMajorPoints<Self::CoordinateType, N>
+            where <Self as Grid<V, N>>::CoordinateType: num::PrimInt + Default{
+        self.bounding_box().points()
+    }
+
+    ///
+    /// The points within the coordinate ranges together with their values in row-major order.
+    ///
+    fn cells(&self) -> Cells<'_, Self, V, Self::CoordinateType, N>
+            where Self: Sized, <Self as Grid<V, N>>::CoordinateType: num::PrimInt + Default{
+        Cells::new(self)
+    }
+
+    fn cropped(&self, bounding_box: BoundingBox<Self::CoordinateType, N>) -> CroppedView<'_, Self, Self::CoordinateType, N>
+            where Self: Sized, <Self as Grid<V, N>>::CoordinateType: num::PrimInt + Default{
+        CroppedView::new(self, bounding_box)
+    }
+
+    ///
+    /// The view of the points with the given coordinate along the axis, e.g. a row for axis 1 of a 2D grid.
+    ///
+    fn slice(&self, axis: usize, coordinate: Self::CoordinateType) -> CroppedView<'_, Self, Self::CoordinateType, N>
+            where Self: Sized, <Self as Grid<V, N>>::CoordinateType: num::PrimInt + Default{
+        let mut coordinate_ranges = *self.coordinate_ranges();
+        coordinate_ranges[axis] = CoordinateRange {lower_bound: coordinate, upper_bound: coordinate + num::one()};
+        CroppedView::new(self, BoundingBox::from(coordinate_ranges))
+    }
+
+    fn transformed(&self, symmetry: SignedPermutation<N>) -> TransformedView<'_, Self, Self::CoordinateType, N>
+            where Self: Sized, <Self as Grid<V, N>>::CoordinateType: num::PrimInt + Default{
+        TransformedView::new(self, symmetry)
+    }
+
+    ///
+    /// The view with the first two axes swapped.
+    ///
+    fn transposed(&self) -> TransformedView<'_, Self, Self::CoordinateType, N>
+            where Self: Sized, <Self as Grid<V, N>>::CoordinateType: num::PrimInt + Default{
+        self.transformed(SignedPermutation::transposition(0, 1))
+    }
+
+    ///
+    /// The view mirrored along the axis.
+    ///
+    fn flipped(&self, axis: usize) -> TransformedView<'_, Self, Self::CoordinateType, N>
+            where Self: Sized, <Self as Grid<V, N>>::CoordinateType: num::PrimInt + Default{
+        self.transformed(SignedPermutation::reflection(axis))
+    }
+
+    ///
+    /// The view rotated by quarter turns in the plane of the first two axes, turning the first axis towards the second one.
+    ///
+    fn rotated(&self, quarter_turns: i64) -> TransformedView<'_, Self, Self::CoordinateType, N>
+            where Self: Sized, <Self as Grid<V, N>>::CoordinateType: num::PrimInt + Default{
+        self.transformed(SignedPermutation::quarter_turn_in_plane(0, 1, quarter_turns))
+    }
 }
 
 pub trait MutGrid<T, const N: usize>: Grid<T, N>{
@@ -69,6 +176,10 @@ impl<T: Hash + Eq + Default + PartialOrd + Copy + Sub<Output=T> + Add<Output=T>
             None => &self.default,
         }
     }
+
+    fn outside_behaviour(&self) -> OutsideBehaviour{
+        OutsideBehaviour::Wrapping
+    }
 }
 
 impl<T: Hash + Eq + Default + PartialOrd + Copy + Sub<Output=T> + Add<Output=T> + Rem<Output=T>, V: PartialEq, const N: usize> MutGrid<V, N> for LoopingGrid<T, V, N>{
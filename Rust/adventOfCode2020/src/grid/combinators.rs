@@ -0,0 +1,202 @@
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+use super::{is_on_main_grid, main_grid_point, DenseGrid, Grid, OutsideBehaviour};
+use crate::space::{Neighbourhood, Point};
+
+///
+/// Operations building new grids or regions from the values of a grid, available for all grids.
+/// Points outside the coordinate ranges behave as described by the `outside_behaviour` of the grid,
+/// i.e. neighbours either wrap around or do not exist.
+///
+pub trait GridCombinators<V, const N: usize>: Grid<V, N> + Sized
+        where <Self as Grid<V, N>>::CoordinateType: num::PrimInt + Default + Hash{
+    ///
+    /// The grid of the values after applying `f`. The result has the same coordinate ranges and outside behaviour.
+    ///
+    fn map<W: Clone>(&self, f: impl Fn(&V) -> W) -> DenseGrid<Self::CoordinateType, W, N>{
+        let values = self.cells()
+            .map(|(point, value)| (point, f(value)));
+        DenseGrid::new(*self.coordinate_ranges(), f(self.outside_value()), self.outside_behaviour(), values)
+    }
+
+    ///
+    /// Combines the values at the same points of two grids, or returns `None` if their coordinate ranges differ.
+    ///
+    fn zip_with<U, W: Clone, G: Grid<U, N, CoordinateType = Self::CoordinateType>>(&self, other: &G, f: impl Fn(&V, &U) -> W) -> Option<DenseGrid<Self::CoordinateType, W, N>>{
+        if self.coordinate_ranges() != other.coordinate_ranges(){
+            return None;
+        }
+        let values = self.cells()
+            .map(|(point, value)| (point, f(value, other.at_point(&point))));
+        let default = f(self.outside_value(), other.at_point(&self.outside_point()));
+        Some(DenseGrid::new(*self.coordinate_ranges(), default, self.outside_behaviour(), values))
+    }
+
+    ///
+    /// Applies the kernel to each value together with the values at the offsets of the stencil, in the order of the offsets.
+    ///
+    fn convolve<W: Clone>(&self, stencil: &Neighbourhood<Self::CoordinateType, N>, kernel: impl Fn(&V, &[&V]) -> W) -> DenseGrid<Self::CoordinateType, W, N>
+            where <Self as Grid<V, N>>::CoordinateType: num::Signed{
+        let values = self.cells()
+            .map(|(point, value)| {
+                let neighbour_values: Vec<&V> = stencil.neighbours(&point)
+                    .map(|neighbour| self.at_point(&neighbour))
+                    .collect();
+                (point, kernel(value, &neighbour_values))
+            });
+        let default = match self.outside_behaviour(){
+            //All neighbours of points far outside are outside as well.
+            OutsideBehaviour::Default => kernel(self.outside_value(), &vec![self.outside_value(); stencil.offsets().len()]),
+            //The outside point wraps onto the grid, so this is the value at the point it wraps to.
+            OutsideBehaviour::Wrapping => {
+                let outside_neighbour_values: Vec<&V> = stencil.neighbours(&self.outside_point())
+                    .map(|neighbour| self.at_point(&neighbour))
+                    .collect();
+                kernel(self.outside_value(), &outside_neighbour_values)
+            },
+        };
+        DenseGrid::new(*self.coordinate_ranges(), default, self.outside_behaviour(), values)
+    }
+
+    ///
+    /// The points reachable from the start by moving to neighbours as long as `is_connected` holds
+    /// for the values before and after the move, in the order of discovery.
+    /// Returns no points if the start is not within the coordinate ranges.
+    ///
+    fn flood_fill(&self, start: &Point<Self::CoordinateType, N>, neighbourhood: &Neighbourhood<Self::CoordinateType, N>, is_connected: impl Fn(&V, &V) -> bool) -> Vec<Point<Self::CoordinateType, N>>
+            where <Self as Grid<V, N>>::CoordinateType: num::Signed{
+        let start = match self.main_grid_point(start){
+            Some(point) => point,
+            None => return vec![],
+        };
+        let mut visited_points = HashSet::new();
+        visited_points.insert(start);
+        let mut region = vec![];
+        let mut points_to_visit = VecDeque::new();
+        points_to_visit.push_back(start);
+        while let Some(point) = points_to_visit.pop_front(){
+            region.push(point);
+            let value = self.at_point(&point);
+            for neighbour in neighbourhood.neighbours(&point).filter_map(|neighbour| self.main_grid_point(&neighbour)){
+                if !visited_points.contains(&neighbour) && is_connected(value, self.at_point(&neighbour)){
+                    visited_points.insert(neighbour);
+                    points_to_visit.push_back(neighbour);
+                }
+            }
+        }
+        region
+    }
+
+    ///
+    /// Partitions the points within the coordinate ranges into the regions of `flood_fill`,
+    /// ordered by their first point in row-major order.
+    /// `is_connected` should be symmetric for the regions to be well-defined.
+    ///
+    fn connected_components(&self, neighbourhood: &Neighbourhood<Self::CoordinateType, N>, is_connected: impl Fn(&V, &V) -> bool) -> Vec<Vec<Point<Self::CoordinateType, N>>>
+            where <Self as Grid<V, N>>::CoordinateType: num::Signed{
+        let mut assigned_points = HashSet::new();
+        let mut components = vec![];
+        for point in self.points(){
+            if assigned_points.contains(&point){
+                continue;
+            }
+            let component = self.flood_fill(&point, neighbourhood, &is_connected);
+            assigned_points.extend(component.iter().copied());
+            components.push(component);
+        }
+        components
+    }
+
+    ///
+    /// The point within the coordinate ranges representing the point, if there is one.
+    ///
+    fn main_grid_point(&self, point: &Point<Self::CoordinateType, N>) -> Option<Point<Self::CoordinateType, N>>{
+        if self.bounding_box().is_empty(){
+            return None;
+        }
+        match self.outside_behaviour(){
+            OutsideBehaviour::Default if is_on_main_grid(point, self.coordinate_ranges()) => Some(*point),
+            OutsideBehaviour::Default => None,
+            OutsideBehaviour::Wrapping => Some(main_grid_point(point, self.coordinate_ranges())),
+        }
+    }
+
+    ///
+    /// A point just outside the coordinate ranges, representing the values outside.
+    ///
+    fn outside_point(&self) -> Point<Self::CoordinateType, N>{
+        self.bounding_box().upper_bound()
+    }
+
+    fn outside_value(&self) -> &V{
+        self.at_point(&self.outside_point())
+    }
+}
+
+impl<V, G: Grid<V, N>, const N: usize> GridCombinators<V, N> for G
+        where G::CoordinateType: num::PrimInt + Default + Hash{}
+
+
+
+#[cfg(test)]
+mod combinators_tests {
+    use super::*;
+    use crate::grid::{CoordinateRange, LoopingGrid, OutsideDefaultGrid, TextCodec};
+
+    fn codec() -> TextCodec<bool>{
+        TextCodec::new(vec![('#', true), ('.', false)])
+    }
+
+    fn digits() -> TextCodec<usize>{
+        TextCodec::new((0..10).map(|digit| (std::char::from_digit(digit as u32, 10).unwrap(), digit)).collect())
+    }
+
+    fn coordinate_ranges() -> [CoordinateRange<i64>; 2]{
+        [CoordinateRange {lower_bound: 0, upper_bound: 4}, CoordinateRange {lower_bound: 0, upper_bound: 3}]
+    }
+
+    fn active_points() -> Vec<(Point<i64, 2>, bool)>{
+        vec![(Point::new([0, 0]), true), (Point::new([1, 0]), true), (Point::new([3, 0]), true), (Point::new([3, 2]), true)]
+    }
+
+    #[test]
+    fn mapping_and_zipping() {
+        let grid = OutsideDefaultGrid::new(coordinate_ranges(), false, active_points().into_iter());
+        let inverted_grid = grid.map(|value| !value);
+        assert_eq!(codec().render(&inverted_grid), "..#.\n####\n###.");
+        assert_eq!(inverted_grid.at_point(&Point::new([-5, 0])), &true);
+        let combined_grid = grid.zip_with(&inverted_grid, |first, second| *first || *second).unwrap();
+        assert_eq!(codec().render(&combined_grid), "####\n####\n####");
+        assert!(grid.zip_with(&grid.slice(1, 0), |first, _| *first).is_none());
+    }
+
+    #[test]
+    fn convolution_depends_on_the_outside_behaviour() {
+        let count_active = |_: &bool, neighbours: &[&bool]| neighbours.iter().filter(|neighbour| ***neighbour).count();
+        let stencil = Neighbourhood::moore();
+        let bounded_grid = OutsideDefaultGrid::new(coordinate_ranges(), false, active_points().into_iter());
+        assert_eq!(digits().render(&bounded_grid.convolve(&stencil, count_active)), "1120\n2232\n0010");
+        let corner_grid = OutsideDefaultGrid::new(coordinate_ranges(), false, std::iter::once((Point::new([3, 2]), true)));
+        assert_eq!(corner_grid.convolve(&stencil, count_active).at_point(&Point::new([100, 100])), &0);
+        let looping_grid = LoopingGrid::new(coordinate_ranges(), false, active_points().into_iter());
+        assert_eq!(digits().render(&looping_grid.convolve(&stencil, count_active)), "3132\n4233\n4232");
+    }
+
+    #[test]
+    fn components() {
+        let grid = OutsideDefaultGrid::new(coordinate_ranges(), false, active_points().into_iter());
+        let is_connected = |first: &bool, second: &bool| *first && *second;
+        let region = grid.flood_fill(&Point::new([1, 0]), &Neighbourhood::von_neumann(), is_connected);
+        assert_eq!(region, vec![Point::new([1, 0]), Point::new([0, 0])]);
+        let active_components = grid.connected_components(&Neighbourhood::von_neumann(), is_connected).into_iter()
+            .filter(|component| *grid.at_point(&component[0]))
+            .count();
+        assert_eq!(active_components, 3);
+        let looping_grid = LoopingGrid::new(coordinate_ranges(), false, active_points().into_iter());
+        let looping_components = looping_grid.connected_components(&Neighbourhood::von_neumann(), is_connected).into_iter()
+            .filter(|component| *looping_grid.at_point(&component[0]))
+            .count();
+        assert_eq!(looping_components, 1);
+    }
+}
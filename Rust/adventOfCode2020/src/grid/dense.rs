@@ -0,0 +1,201 @@
+use std::hash::Hash;
+
+use super::{is_on_main_grid, main_grid_point, CoordinateRange, Grid, LoopingGrid, MutGrid, OutsideDefaultGrid};
+use crate::space::{BoundingBox, Point};
+
+///
+/// What a grid does with points outside of its coordinate ranges.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutsideBehaviour{
+    ///
+    /// Points outside the coordinate ranges have the default value and cannot be changed, like in `OutsideDefaultGrid`.
+    ///
+    Default,
+    ///
+    /// The grid repeats periodically in all directions, like `LoopingGrid`.
+    ///
+    Wrapping,
+}
+
+///
+/// Grid storing a value for every point within its coordinate ranges in a flat vector, the first coordinate changing fastest.
+/// This is faster than the hash map based grids if most points are in use.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid<T, V, const N: usize>{
+    coordinate_ranges: [CoordinateRange<T>; N],
+    values: Vec<V>,
+    default: V,
+    outside_behaviour: OutsideBehaviour,
+}
+
+impl<T: num::PrimInt + Default, V: Clone, const N: usize> DenseGrid<T, V, N>{
+    ///
+    /// Values at points outside the coordinate ranges are ignored, unless the grid wraps around.
+    ///
+    pub fn new(coordinate_ranges: [CoordinateRange<T>; N], default: V, outside_behaviour: OutsideBehaviour, values: impl Iterator<Item = (Point<T, N>, V)>) -> DenseGrid<T, V, N>{
+        let cell_count = BoundingBox::from(coordinate_ranges).volume()
+            .to_usize()
+            .expect("The number of cells in a dense grid must fit into a usize.");
+        let mut grid = DenseGrid {
+            coordinate_ranges,
+            values: vec![default.clone(); cell_count],
+            default,
+            outside_behaviour,
+        };
+        for (point, value) in values{
+            grid.set_point(&point, value);
+        }
+        grid
+    }
+
+    pub fn default_value(&self) -> &V{
+        &self.default
+    }
+
+    ///
+    /// The position of the point in the vector of values, if the point is stored in the grid.
+    ///
+    fn index(&self, point: &Point<T, N>) -> Option<usize>{
+        let reference_point = match self.outside_behaviour{
+            OutsideBehaviour::Default if !is_on_main_grid(point, &self.coordinate_ranges) => return None,
+            OutsideBehaviour::Default => *point,
+            OutsideBehaviour::Wrapping => main_grid_point(point, &self.coordinate_ranges),
+        };
+        let mut index = 0;
+        let mut stride = 1;
+        for (axis, range) in self.coordinate_ranges.iter().enumerate(){
+            let offset = (reference_point[axis] - range.lower_bound).to_usize()?;
+            let width = (range.upper_bound - range.lower_bound).to_usize()?;
+            index += offset * stride;
+            stride *= width;
+        }
+        Some(index)
+    }
+}
+
+impl<T: num::PrimInt + Default, V: Clone, const N: usize> Grid<V, N> for DenseGrid<T, V, N>{
+    type CoordinateType = T;
+
+    fn coordinate_ranges(&self) -> &[CoordinateRange<Self::CoordinateType>; N]{
+        &self.coordinate_ranges
+    }
+
+    fn at_point(&self, point: &Point<Self::CoordinateType, N>) -> &V{
+        match self.index(point){
+            Some(index) => &self.values[index],
+            None => &self.default,
+        }
+    }
+
+    fn outside_behaviour(&self) -> OutsideBehaviour{
+        self.outside_behaviour
+    }
+}
+
+impl<T: num::PrimInt + Default, V: Clone, const N: usize> MutGrid<V, N> for DenseGrid<T, V, N>{
+    fn set_point(&mut self, point: &Point<T, N>, value: V){
+        if let Some(index) = self.index(point){
+            self.values[index] = value;
+        }
+    }
+}
+
+impl<T: num::PrimInt + Default, V, const N: usize> IntoIterator for DenseGrid<T, V, N>{
+    type Item = (Point<T, N>, V);
+    type IntoIter = std::iter::Zip<crate::space::RowMajorPoints<T, N>, std::vec::IntoIter<V>>;
+
+    ///
+    /// Iterates over all points within the coordinate ranges together with their values.
+    ///
+    fn into_iter(self) -> Self::IntoIter{
+        BoundingBox::from(self.coordinate_ranges)
+            .points()
+            .zip(self.values)
+    }
+}
+
+impl<T: num::PrimInt + Default + Hash, V: Clone + PartialEq, const N: usize> From<OutsideDefaultGrid<T, V, N>> for DenseGrid<T, V, N>{
+    fn from(grid: OutsideDefaultGrid<T, V, N>) -> Self{
+        DenseGrid::new(grid.coordinate_ranges, grid.default, OutsideBehaviour::Default, grid.map.into_iter())
+    }
+}
+
+impl<T: num::PrimInt + Default + Hash, V: Clone + PartialEq, const N: usize> From<LoopingGrid<T, V, N>> for DenseGrid<T, V, N>{
+    fn from(grid: LoopingGrid<T, V, N>) -> Self{
+        DenseGrid::new(grid.coordinate_ranges, grid.default, OutsideBehaviour::Wrapping, grid.map.into_iter())
+    }
+}
+
+impl<T: num::PrimInt + Default + Hash, V: Clone + PartialEq, const N: usize> From<DenseGrid<T, V, N>> for OutsideDefaultGrid<T, V, N>{
+    fn from(grid: DenseGrid<T, V, N>) -> Self{
+        let coordinate_ranges = grid.coordinate_ranges;
+        let default = grid.default.clone();
+        OutsideDefaultGrid::new(coordinate_ranges, default, grid.into_iter())
+    }
+}
+
+impl<T: num::PrimInt + Default + Hash, V: Clone + PartialEq, const N: usize> From<DenseGrid<T, V, N>> for LoopingGrid<T, V, N>{
+    fn from(grid: DenseGrid<T, V, N>) -> Self{
+        let coordinate_ranges = grid.coordinate_ranges;
+        let default = grid.default.clone();
+        LoopingGrid::new(coordinate_ranges, default, grid.into_iter())
+    }
+}
+
+
+
+#[cfg(test)]
+mod dense_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn coordinate_ranges() -> [CoordinateRange<i64>; 2]{
+        [
+            CoordinateRange {lower_bound: -1, upper_bound: 2},
+            CoordinateRange {lower_bound: 0, upper_bound: 2},
+        ]
+    }
+
+    fn values() -> Vec<(Point<i64, 2>, u8)>{
+        vec![(Point::new([-1, 0]), 1), (Point::new([1, 1]), 2), (Point::new([0, 1]), 3)]
+    }
+
+    #[rstest]
+    #[case(OutsideBehaviour::Default, Point::new([1, 1]), 2)]
+    #[case(OutsideBehaviour::Default, Point::new([2, 1]), 0)]
+    #[case(OutsideBehaviour::Default, Point::new([-2, 0]), 0)]
+    #[case(OutsideBehaviour::Wrapping, Point::new([2, 0]), 1)]
+    #[case(OutsideBehaviour::Wrapping, Point::new([-3, -1]), 3)]
+    fn values_at_points(#[case] outside_behaviour: OutsideBehaviour, #[case] point: Point<i64, 2>, #[case] expected_value: u8) {
+        let grid = DenseGrid::new(coordinate_ranges(), 0, outside_behaviour, values().into_iter());
+        assert_eq!(grid.at_point(&point), &expected_value);
+    }
+
+    #[test]
+    fn setting_points_outside_the_grid() {
+        let mut grid = DenseGrid::new(coordinate_ranges(), 0, OutsideBehaviour::Default, values().into_iter());
+        grid.set_point(&Point::new([5, 5]), 7);
+        assert_eq!(grid.at_point(&Point::new([5, 5])), &0);
+        let mut wrapping_grid = DenseGrid::new(coordinate_ranges(), 0, OutsideBehaviour::Wrapping, values().into_iter());
+        wrapping_grid.set_point(&Point::new([5, 5]), 7);
+        assert_eq!(wrapping_grid.at_point(&Point::new([-1, 1])), &7);
+    }
+
+    #[test]
+    fn conversions_between_dense_and_sparse_grids() {
+        let sparse_grid = OutsideDefaultGrid::new(coordinate_ranges(), 0, values().into_iter());
+        let dense_grid = DenseGrid::from(sparse_grid);
+        assert_eq!(dense_grid, DenseGrid::new(coordinate_ranges(), 0, OutsideBehaviour::Default, values().into_iter()));
+        let mut round_trip_values: Vec<(Point<i64, 2>, u8)> = OutsideDefaultGrid::from(dense_grid).into_iter().collect();
+        round_trip_values.sort_by_key(|(_, value)| *value);
+        assert_eq!(round_trip_values, values());
+
+        let looping_grid = LoopingGrid::new(coordinate_ranges(), 0, values().into_iter());
+        let dense_looping_grid = DenseGrid::from(looping_grid);
+        assert_eq!(dense_looping_grid.outside_behaviour(), OutsideBehaviour::Wrapping);
+        assert_eq!(dense_looping_grid.at_point(&Point::new([3, 1])), &3);
+        assert_eq!(LoopingGrid::from(dense_looping_grid).at_point(&Point::new([6, 3])), &3);
+    }
+}
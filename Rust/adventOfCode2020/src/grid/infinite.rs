@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use super::{CoordinateRange, Grid, MutGrid};
+use crate::space::{BoundingBox, Point};
+
+///
+/// The number of points along each axis of a chunk.
+///
+const CHUNK_SIZE: usize = 8;
+
+struct Chunk<V>{
+    values: Vec<V>,
+    non_default_count: usize,
+}
+
+///
+/// Grid without bounds, in which all points not set otherwise have the default value.
+/// The values are stored in cubic chunks, which are only allocated while they contain a value other than the default.
+/// The coordinate ranges always form the smallest box containing all points with other values.
+///
+pub struct InfiniteGrid<T, V, const N: usize>{
+    coordinate_ranges: [CoordinateRange<T>; N],
+    chunks: HashMap<Point<T, N>, Chunk<V>>,
+    //For each axis, the number of points with other values by their coordinate along the axis.
+    coordinate_counts: [BTreeMap<T, usize>; N],
+    default: V,
+}
+
+impl<T: num::PrimInt + num::Integer + Default + Hash, V: Clone + PartialEq, const N: usize> InfiniteGrid<T, V, N>{
+    pub fn new(default: V, non_default_values: impl Iterator<Item = (Point<T, N>, V)>) -> InfiniteGrid<T, V, N>{
+        let mut grid = InfiniteGrid {
+            coordinate_ranges: [CoordinateRange::default(); N],
+            chunks: HashMap::new(),
+            coordinate_counts: [(); N].map(|_| BTreeMap::new()),
+            default,
+        };
+        for (point, value) in non_default_values{
+            grid.set_point(&point, value);
+        }
+        grid
+    }
+
+    ///
+    /// The number of points with a value other than the default.
+    ///
+    pub fn non_default_count(&self) -> usize{
+        self.chunks.values()
+            .map(|chunk| chunk.non_default_count)
+            .sum()
+    }
+
+    ///
+    /// The points with a value other than the default together with their values, in no particular order.
+    ///
+    pub fn non_default_cells(&self) -> impl Iterator<Item = (Point<T, N>, &V)> + '_{
+        self.chunks.iter()
+            .flat_map(move |(chunk_index, chunk)| chunk.values.iter()
+                .enumerate()
+                .filter(move |(_, value)| **value != self.default)
+                .map(move |(index, value)| (point_in_chunk(chunk_index, index), value)))
+    }
+
+    fn add_coordinates(&mut self, point: &Point<T, N>){
+        for (axis, counts) in self.coordinate_counts.iter_mut().enumerate(){
+            *counts.entry(point[axis]).or_insert(0) += 1;
+        }
+        let mut bounding_box = BoundingBox::from(self.coordinate_ranges);
+        bounding_box.include(*point);
+        self.coordinate_ranges = bounding_box.into();
+    }
+
+    fn remove_coordinates(&mut self, point: &Point<T, N>){
+        for (axis, counts) in self.coordinate_counts.iter_mut().enumerate(){
+            if let Some(count) = counts.get_mut(&point[axis]){
+                *count -= 1;
+                if *count == 0{
+                    counts.remove(&point[axis]);
+                }
+            }
+        }
+        for (range, counts) in self.coordinate_ranges.iter_mut().zip(self.coordinate_counts.iter()){
+            *range = match (counts.keys().next(), counts.keys().next_back()){
+                (Some(lower_bound), Some(upper_bound)) => CoordinateRange {lower_bound: *lower_bound, upper_bound: *upper_bound + T::one()},
+                _ => CoordinateRange::default(),
+            };
+        }
+    }
+}
+
+fn chunk_position<T: num::PrimInt + num::Integer + Default, const N: usize>(point: &Point<T, N>) -> (Point<T, N>, usize){
+    let chunk_size = T::from(CHUNK_SIZE).unwrap();
+    let mut chunk_index = [T::default(); N];
+    let mut index_in_chunk = 0;
+    for axis in (0..N).rev(){
+        let (quotient, remainder) = point[axis].div_mod_floor(&chunk_size);
+        chunk_index[axis] = quotient;
+        index_in_chunk = index_in_chunk * CHUNK_SIZE + remainder.to_usize().unwrap();
+    }
+    (Point::new(chunk_index), index_in_chunk)
+}
+
+fn point_in_chunk<T: num::PrimInt + num::Integer + Default, const N: usize>(chunk_index: &Point<T, N>, index_in_chunk: usize) -> Point<T, N>{
+    let chunk_size = T::from(CHUNK_SIZE).unwrap();
+    let mut arr = [T::default(); N];
+    let mut remaining_index = index_in_chunk;
+    for (axis, coordinate) in arr.iter_mut().enumerate(){
+        *coordinate = chunk_index[axis] * chunk_size + T::from(remaining_index % CHUNK_SIZE).unwrap();
+        remaining_index /= CHUNK_SIZE;
+    }
+    Point::new(arr)
+}
+
+impl<T: num::PrimInt + num::Integer + Default + Hash, V: Clone + PartialEq, const N: usize> Grid<V, N> for InfiniteGrid<T, V, N>{
+    type CoordinateType = T;
+
+    fn coordinate_ranges(&self) -> &[CoordinateRange<Self::CoordinateType>; N]{
+        &self.coordinate_ranges
+    }
+
+    fn at_point(&self, point: &Point<Self::CoordinateType, N>) -> &V{
+        let (chunk_index, index_in_chunk) = chunk_position(point);
+        match self.chunks.get(&chunk_index){
+            Some(chunk) => &chunk.values[index_in_chunk],
+            None => &self.default,
+        }
+    }
+}
+
+impl<T: num::PrimInt + num::Integer + Default + Hash, V: Clone + PartialEq, const N: usize> MutGrid<V, N> for InfiniteGrid<T, V, N>{
+    fn set_point(&mut self, point: &Point<T, N>, value: V){
+        let (chunk_index, index_in_chunk) = chunk_position(point);
+        let is_default = value == self.default;
+        if is_default && !self.chunks.contains_key(&chunk_index){
+            return;
+        }
+        let default = self.default.clone();
+        let chunk = self.chunks.entry(chunk_index)
+            .or_insert_with(|| Chunk {values: vec![default; CHUNK_SIZE.pow(N as u32)], non_default_count: 0});
+        let was_default = chunk.values[index_in_chunk] == self.default;
+        chunk.values[index_in_chunk] = value;
+        match (was_default, is_default){
+            (true, false) => {
+                chunk.non_default_count += 1;
+                self.add_coordinates(point);
+            },
+            (false, true) => {
+                chunk.non_default_count -= 1;
+                if chunk.non_default_count == 0{
+                    self.chunks.remove(&chunk_index);
+                }
+                self.remove_coordinates(point);
+            },
+            _ => (),
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod infinite_tests {
+    use super::*;
+    use crate::grid::TextCodec;
+
+    #[test]
+    fn the_bounds_follow_the_non_default_values() {
+        let mut grid = InfiniteGrid::new(false, vec![(Point::new([-20, 3]), true)].into_iter());
+        assert_eq!(BoundingBox::from(*grid.coordinate_ranges()), BoundingBox::new(Point::new([-20, 3]), Point::new([-19, 4])));
+        grid.set_point(&Point::new([5, -9]), true);
+        grid.set_point(&Point::new([0, 0]), true);
+        assert_eq!(BoundingBox::from(*grid.coordinate_ranges()), BoundingBox::new(Point::new([-20, -9]), Point::new([6, 4])));
+        grid.set_point(&Point::new([-20, 3]), false);
+        assert_eq!(BoundingBox::from(*grid.coordinate_ranges()), BoundingBox::new(Point::new([0, -9]), Point::new([6, 1])));
+        assert_eq!(grid.non_default_count(), 2);
+        grid.set_point(&Point::new([5, -9]), false);
+        grid.set_point(&Point::new([0, 0]), false);
+        assert_eq!(grid.non_default_count(), 0);
+        assert!(grid.bounding_box().is_empty());
+    }
+
+    #[test]
+    fn clearing_the_boundary_point_by_point() {
+        let mut grid = InfiniteGrid::new(0, (0..3).flat_map(|y| (0..100).map(move |x| (Point::new([x, y]), 1))));
+        for x in 0..100{
+            grid.set_point(&Point::new([x, 2]), 0);
+            assert_eq!(grid.coordinate_ranges()[1].upper_bound, if x < 99 { 3 } else { 2 });
+        }
+        for y in 0..2{
+            grid.set_point(&Point::new([0, y]), 0);
+        }
+        assert_eq!(BoundingBox::from(*grid.coordinate_ranges()), BoundingBox::new(Point::new([1, 0]), Point::new([100, 2])));
+    }
+
+    #[test]
+    fn values_across_chunks() {
+        let points = [Point::new([-1, -1, 0]), Point::new([7, 8, -9]), Point::new([100, 0, 3])];
+        let grid = InfiniteGrid::new(0, points.iter().map(|point| (*point, point[0])));
+        for point in points.iter(){
+            assert_eq!(grid.at_point(point), &point[0]);
+        }
+        assert_eq!(grid.at_point(&Point::new([8, 8, -9])), &0);
+        let mut cells: Vec<(Point<i64, 3>, i64)> = grid.non_default_cells().map(|(point, value)| (point, *value)).collect();
+        cells.sort_by_key(|(_, value)| *value);
+        assert_eq!(cells, vec![(Point::new([-1, -1, 0]), -1), (Point::new([7, 8, -9]), 7), (Point::new([100, 0, 3]), 100)]);
+    }
+
+    #[test]
+    fn rendering() {
+        let grid = InfiniteGrid::new(false, vec![(Point::new([-1, 5]), true), (Point::new([1, 6]), true)].into_iter());
+        let codec = TextCodec::new(vec![('#', true), ('.', false)]);
+        assert_eq!(codec.render(&grid), "#..\n..#");
+    }
+}
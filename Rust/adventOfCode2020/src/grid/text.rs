@@ -0,0 +1,155 @@
+use std::fmt::Display;
+
+use super::{CoordinateRange, DenseGrid, Grid, OutsideBehaviour};
+use crate::error::ParseError;
+use crate::space::Point;
+
+///
+/// Translates between the values in a grid and the characters in their ASCII representation,
+/// in which the first coordinate increases along each line and the second one from line to line.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextCodec<V>{
+    mapping: Vec<(char, V)>,
+}
+
+impl<V: Clone + PartialEq> TextCodec<V>{
+    ///
+    /// If a value occurs with several characters, the first one is used for rendering.
+    ///
+    pub fn new(mapping: Vec<(char, V)>) -> TextCodec<V>{
+        TextCodec {mapping}
+    }
+
+    pub fn value(&self, character: char) -> Option<&V>{
+        self.mapping.iter()
+            .find(|(mapped_character, _)| *mapped_character == character)
+            .map(|(_, value)| value)
+    }
+
+    pub fn character(&self, value: &V) -> Option<char>{
+        self.mapping.iter()
+            .find(|(_, mapped_value)| mapped_value == value)
+            .map(|(character, _)| *character)
+    }
+
+    ///
+    /// Parses all characters of a map into values at their points. All lines must have the same length.
+    ///
+    pub fn parse_values<T: num::PrimInt>(&self, text: &str) -> Result<Vec<(Point<T, 2>, V)>, ParseError>{
+        let mut values = vec![];
+        let width = text.lines().next().map_or(0, |line| line.chars().count());
+        for (y, line) in text.lines().enumerate(){
+            if line.chars().count() != width{
+                return Err(ParseError::at(y, 0, &format!("Expected a line of width {}.", width)));
+            }
+            for (x, character) in line.chars().enumerate(){
+                let value = self.value(character)
+                    .ok_or_else(|| ParseError::at(y, x, &format!("Unexpected character '{}' on the map.", character)))?;
+                let point = Point::new([coordinate(x, y, x)?, coordinate(y, y, x)?]);
+                values.push((point, value.clone()));
+            }
+        }
+        Ok(values)
+    }
+
+    ///
+    /// Parses a map into a grid with the lines starting at `x = 0` and the first line at `y = 0`.
+    ///
+    pub fn parse<T: num::PrimInt + Default>(&self, text: &str, default: V, outside_behaviour: OutsideBehaviour) -> Result<DenseGrid<T, V, 2>, ParseError>{
+        let values = self.parse_values(text)?;
+        let height = text.lines().count();
+        let width = text.lines().next().map_or(0, |line| line.chars().count());
+        let coordinate_ranges = [
+            CoordinateRange {lower_bound: T::zero(), upper_bound: coordinate(width, height, width)?},
+            CoordinateRange {lower_bound: T::zero(), upper_bound: coordinate(height, height, width)?},
+        ];
+        Ok(DenseGrid::new(coordinate_ranges, default, outside_behaviour, values.into_iter()))
+    }
+
+    ///
+    /// Renders the points within the coordinate ranges of the grid. Grids with more than two dimensions are rendered
+    /// as one 2D slice after the other, each labelled with its remaining coordinates, e.g. `z=-1, w=0`.
+    /// Values without a character are rendered as `?`.
+    ///
+    pub fn render<G: Grid<V, N>, const N: usize>(&self, grid: &G) -> String
+            where G::CoordinateType: num::PrimInt + Default + Display{
+        let bounding_box = grid.bounding_box();
+        let lower_bound = bounding_box.lower_bound();
+        let mut text = String::new();
+        for point in bounding_box.points(){
+            let is_start_of_line = N == 0 || point[0] == lower_bound[0];
+            let is_start_of_slice = is_start_of_line && (N < 2 || point[1] == lower_bound[1]);
+            if is_start_of_slice && N > 2{
+                if !text.is_empty(){
+                    text.push_str("\n\n");
+                }
+                text.push_str(&slice_label(&point));
+                text.push('\n');
+            } else if is_start_of_line && !text.is_empty(){
+                text.push('\n');
+            }
+            text.push(self.character(grid.at_point(&point)).unwrap_or('?'));
+        }
+        text
+    }
+}
+
+fn coordinate<T: num::PrimInt>(index: usize, line_index: usize, column_index: usize) -> Result<T, ParseError>{
+    T::from(index)
+        .ok_or_else(|| ParseError::at(line_index, column_index, "The map is too large."))
+}
+
+fn slice_label<T: Display, const N: usize>(point: &Point<T, N>) -> String{
+    let labels: Vec<String> = (2..N)
+        .map(|axis| match axis{
+            2 => format!("z={}", point[axis]),
+            3 => format!("w={}", point[axis]),
+            _ => format!("x{}={}", axis, point[axis]),
+        })
+        .collect();
+    labels.join(", ")
+}
+
+
+
+#[cfg(test)]
+mod text_tests {
+    use super::*;
+    use crate::grid::OutsideDefaultGrid;
+    use rstest::rstest;
+
+    fn codec() -> TextCodec<bool>{
+        TextCodec::new(vec![('#', true), ('.', false)])
+    }
+
+    #[test]
+    fn parsing_and_rendering_round_trip() {
+        let text = ".#.\n..#\n###";
+        let grid = codec().parse::<i64>(text, false, OutsideBehaviour::Default).unwrap();
+        assert!(*grid.at_point(&Point::new([1, 0])));
+        assert!(!*grid.at_point(&Point::new([0, 1])));
+        assert_eq!(codec().render(&grid), text);
+    }
+
+    #[rstest]
+    #[case(".#\n#x", 2, 2)]
+    #[case(".#\n#", 2, 1)]
+    fn invalid_maps(#[case] text: &str, #[case] expected_line: usize, #[case] expected_column: usize) {
+        let error = codec().parse_values::<i64>(text).unwrap_err();
+        assert_eq!((error.line, error.column), (expected_line, expected_column));
+    }
+
+    #[test]
+    fn rendering_slices_of_higher_dimensional_grids() {
+        let coordinate_ranges = [
+            CoordinateRange {lower_bound: 0, upper_bound: 2},
+            CoordinateRange {lower_bound: 0, upper_bound: 2},
+            CoordinateRange {lower_bound: -1, upper_bound: 1},
+            CoordinateRange {lower_bound: 0, upper_bound: 1},
+        ];
+        let active_points = vec![Point::new([0, 0, -1, 0]), Point::new([1, 1, 0, 0])];
+        let grid = OutsideDefaultGrid::new(coordinate_ranges, false, active_points.into_iter().map(|point| (point, true)));
+        assert_eq!(codec().render(&grid), "z=-1, w=0\n#.\n..\n\nz=0, w=0\n..\n.#");
+    }
+}
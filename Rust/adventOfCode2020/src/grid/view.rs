@@ -0,0 +1,223 @@
+use std::marker::PhantomData;
+
+use super::{is_on_main_grid, main_grid_point, CoordinateRange, Grid, OutsideBehaviour};
+use crate::space::{BoundingBox, Point, RowMajorPoints, SignedPermutation};
+
+///
+/// Borrowing iterator over the points within the coordinate ranges of a grid together with their values, in row-major order.
+///
+pub struct Cells<'a, G, V, T, const N: usize>{
+    grid: &'a G,
+    points: RowMajorPoints<T, N>,
+    value_type: PhantomData<&'a V>,
+}
+
+impl<'a, G: Grid<V, N, CoordinateType = T>, V, T: num::PrimInt + Default, const N: usize> Cells<'a, G, V, T, N>{
+    pub(super) fn new(grid: &'a G) -> Cells<'a, G, V, T, N>{
+        Cells {grid, points: grid.points(), value_type: PhantomData}
+    }
+}
+
+impl<'a, G: Grid<V, N, CoordinateType = T>, V: 'a, T: num::PrimInt + Default, const N: usize> Iterator for Cells<'a, G, V, T, N>{
+    type Item = (Point<T, N>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item>{
+        let point = self.points.next()?;
+        Some((point, self.grid.at_point(&point)))
+    }
+}
+
+///
+/// View restricting a grid to a box within its coordinate ranges, without copying any values.
+/// The view has the outside behaviour of the underlying grid, so points outside the box either
+/// repeat the box or have the value outside the underlying grid.
+///
+pub struct CroppedView<'a, G, T, const N: usize>{
+    grid: &'a G,
+    coordinate_ranges: [CoordinateRange<T>; N],
+    //A point outside the underlying grid, to look up the value outside the view.
+    outside_point: Point<T, N>,
+}
+
+impl<'a, G: Grid<V, N, CoordinateType = T>, V, T: num::PrimInt + Default, const N: usize> Grid<V, N> for CroppedView<'a, G, T, N>{
+    type CoordinateType = T;
+
+    fn coordinate_ranges(&self) -> &[CoordinateRange<Self::CoordinateType>; N]{
+        &self.coordinate_ranges
+    }
+
+    fn at_point(&self, point: &Point<Self::CoordinateType, N>) -> &V{
+        match self.outside_behaviour(){
+            OutsideBehaviour::Wrapping => self.grid.at_point(&main_grid_point(point, &self.coordinate_ranges)),
+            OutsideBehaviour::Default if is_on_main_grid(point, &self.coordinate_ranges) => self.grid.at_point(point),
+            OutsideBehaviour::Default => self.grid.at_point(&self.outside_point),
+        }
+    }
+
+    ///
+    /// An empty view has nothing to repeat, so all points are outside.
+    ///
+    fn outside_behaviour(&self) -> OutsideBehaviour{
+        if self.bounding_box().is_empty(){
+            OutsideBehaviour::Default
+        } else {
+            self.grid.outside_behaviour()
+        }
+    }
+}
+
+impl<'a, G, T: num::PrimInt + Default, const N: usize> CroppedView<'a, G, T, N>{
+    ///
+    /// The box is clipped to the coordinate ranges of the grid.
+    ///
+    pub(super) fn new<V>(grid: &'a G, bounding_box: BoundingBox<T, N>) -> CroppedView<'a, G, T, N>
+            where G: Grid<V, N, CoordinateType = T>{
+        let clipped_box = grid.bounding_box()
+            .intersect(&bounding_box)
+            .unwrap_or_else(|| BoundingBox::new(bounding_box.lower_bound(), bounding_box.lower_bound()));
+        CroppedView {grid, coordinate_ranges: clipped_box.into(), outside_point: grid.bounding_box().upper_bound()}
+    }
+}
+
+///
+/// View of a grid after applying a symmetry, e.g. a rotation or reflection, without copying any values.
+/// Along each axis, the coordinate ranges of the view start at the lower bound of the underlying axis it comes from.
+///
+pub struct TransformedView<'a, G, T, const N: usize>{
+    grid: &'a G,
+    symmetry: SignedPermutation<N>,
+    coordinate_ranges: [CoordinateRange<T>; N],
+    //The sum of the first and the last coordinate along each axis of the underlying grid, to reflect coordinates.
+    reflection_sums: [T; N],
+}
+
+impl<'a, G, T: num::PrimInt + Default, const N: usize> TransformedView<'a, G, T, N>{
+    pub(super) fn new<V>(grid: &'a G, symmetry: SignedPermutation<N>) -> TransformedView<'a, G, T, N>
+            where G: Grid<V, N, CoordinateType = T>{
+        let underlying_ranges = grid.coordinate_ranges();
+        let mut reflection_sums = [T::zero(); N];
+        for (sum, range) in reflection_sums.iter_mut().zip(underlying_ranges.iter()){
+            *sum = range.lower_bound + range.upper_bound - T::one();
+        }
+        let mut coordinate_ranges = [CoordinateRange::default(); N];
+        for (axis, range) in coordinate_ranges.iter_mut().enumerate(){
+            *range = underlying_ranges[symmetry.source_axis(axis)];
+        }
+        TransformedView {grid, symmetry, coordinate_ranges, reflection_sums}
+    }
+
+    ///
+    /// The point of the underlying grid shown at the point of the view.
+    ///
+    pub fn underlying_point(&self, point: &Point<T, N>) -> Point<T, N>{
+        let mut arr = [T::zero(); N];
+        for axis in 0..N{
+            let source_axis = self.symmetry.source_axis(axis);
+            arr[source_axis] = if self.symmetry.is_negated(axis){
+                //Without a representation, the reflected coordinate is far outside the underlying grid.
+                self.reflection_sums[source_axis].checked_sub(&point[axis]).unwrap_or_else(T::max_value)
+            } else {
+                point[axis]
+            };
+        }
+        Point::new(arr)
+    }
+}
+
+impl<'a, G: Grid<V, N, CoordinateType = T>, V, T: num::PrimInt + Default, const N: usize> Grid<V, N> for TransformedView<'a, G, T, N>{
+    type CoordinateType = T;
+
+    fn coordinate_ranges(&self) -> &[CoordinateRange<Self::CoordinateType>; N]{
+        &self.coordinate_ranges
+    }
+
+    fn at_point(&self, point: &Point<Self::CoordinateType, N>) -> &V{
+        self.grid.at_point(&self.underlying_point(point))
+    }
+
+    ///
+    /// Reflecting or permuting the axes of a repeating grid results in a repeating grid.
+    ///
+    fn outside_behaviour(&self) -> OutsideBehaviour{
+        self.grid.outside_behaviour()
+    }
+}
+
+
+
+#[cfg(test)]
+mod view_tests {
+    use super::*;
+    use crate::grid::{OutsideBehaviour, TextCodec};
+    use rstest::rstest;
+
+    fn codec() -> TextCodec<char>{
+        TextCodec::new(vec![('a', 'a'), ('b', 'b'), ('c', 'c'), ('d', 'd'), ('e', 'e'), ('f', 'f'), ('.', '.')])
+    }
+
+    fn grid() -> crate::grid::DenseGrid<i64, char, 2>{
+        codec().parse("abc\ndef", '.', OutsideBehaviour::Default).unwrap()
+    }
+
+    #[test]
+    fn borrowed_iteration() {
+        let grid = grid();
+        let values: String = grid.cells().map(|(_, value)| *value).collect();
+        assert_eq!(values, "abcdef");
+        assert_eq!(grid.points().last(), Some(Point::new([2, 1])));
+    }
+
+    #[rstest]
+    #[case(0, 1, "b\ne")]
+    #[case(1, 1, "def")]
+    #[case(1, 5, "")]
+    fn slices(#[case] axis: usize, #[case] coordinate: i64, #[case] expected_text: &str) {
+        let grid = grid();
+        assert_eq!(codec().render(&grid.slice(axis, coordinate)), expected_text);
+    }
+
+    #[test]
+    fn cropping() {
+        let grid = grid();
+        let cropped_view = grid.cropped(BoundingBox::new(Point::new([1, 0]), Point::new([5, 2])));
+        assert_eq!(codec().render(&cropped_view), "bc\nef");
+        assert_eq!(cropped_view.at_point(&Point::new([0, 0])), &'.');
+    }
+
+    #[test]
+    fn views_keep_the_outside_behaviour() {
+        let looping_grid = codec().parse("abc\ndef", '.', OutsideBehaviour::Wrapping).unwrap();
+        let cropped_view = looping_grid.cropped(BoundingBox::new(Point::new([1, 0]), Point::new([3, 2])));
+        assert_eq!(cropped_view.outside_behaviour(), OutsideBehaviour::Wrapping);
+        assert_eq!(cropped_view.at_point(&Point::new([3, 0])), &'b');
+        assert_eq!(cropped_view.at_point(&Point::new([0, 1])), &'f');
+        let rotated_view = looping_grid.rotated(1);
+        assert_eq!(rotated_view.outside_behaviour(), OutsideBehaviour::Wrapping);
+        assert_eq!(rotated_view.at_point(&Point::new([2, 0])), rotated_view.at_point(&Point::new([0, 0])));
+        assert_eq!(grid().rotated(1).outside_behaviour(), OutsideBehaviour::Default);
+        assert_eq!(grid().slice(1, 5).outside_behaviour(), OutsideBehaviour::Default);
+    }
+
+    #[rstest]
+    #[case(SignedPermutation::identity(), "abc\ndef")]
+    #[case(SignedPermutation::transposition(0, 1), "ad\nbe\ncf")]
+    #[case(SignedPermutation::reflection(0), "cba\nfed")]
+    #[case(SignedPermutation::reflection(1), "def\nabc")]
+    #[case(SignedPermutation::quarter_turn_in_plane(0, 1, 1), "da\neb\nfc")]
+    #[case(SignedPermutation::quarter_turn_in_plane(0, 1, 2), "fed\ncba")]
+    fn transformations(#[case] symmetry: SignedPermutation<2>, #[case] expected_text: &str) {
+        let grid = grid();
+        let view = grid.transformed(symmetry);
+        assert_eq!(codec().render(&view), expected_text);
+        assert_eq!(view.at_point(&Point::new([7, 7])), &'.');
+    }
+
+    #[test]
+    fn views_of_views() {
+        let grid = grid();
+        let rotated_view = grid.rotated(1);
+        let cropped_view = rotated_view.cropped(BoundingBox::new(Point::new([0, 1]), Point::new([2, 3])));
+        assert_eq!(codec().render(&cropped_view), "eb\nfc");
+        assert_eq!(codec().render(&grid.transposed().flipped(0)), codec().render(&rotated_view));
+    }
+}
@@ -1,30 +1,88 @@
 use std::{path::Path, path::PathBuf};
 use std::fs;
+use std::io::{self, Read};
+
+use crate::error::PuzzleError;
+use crate::input_layout::InputLayout;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PuzzleConfiguration {
+    pub year: i32,
     pub day: i32,
     pub part: i32
 }
 
-pub fn puzzle_input(config: &PuzzleConfiguration) -> Option<String>{
-    let path: PathBuf = puzzle_file_path(&config);
-    match fs::read_to_string(path){
-        Err(_) => None,
-        Ok(text) => Some(text)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource{
+    PuzzleFile,
+    File(PathBuf),
+    StandardInput,
+}
+
+pub fn puzzle_input(layout: &InputLayout, config: &PuzzleConfiguration) -> Result<String, PuzzleError>{
+    let path: PathBuf = layout.puzzle_file_path(config);
+    read_file(&path)
+}
+
+pub fn input_from_source(source: &InputSource, layout: &InputLayout, config: &PuzzleConfiguration) -> Result<String, PuzzleError>{
+    match source{
+        InputSource::PuzzleFile => puzzle_input(layout, config),
+        InputSource::File(path) => read_file(path),
+        InputSource::StandardInput => {
+            let mut text = String::new();
+            match io::stdin().read_to_string(&mut text){
+                Err(error) => Err(PuzzleError::Io {source: source_description(source, layout, config), error}),
+                Ok(_) => Ok(text)
+            }
+        },
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, PuzzleError>{
+    fs::read_to_string(path)
+        .map_err(|error| PuzzleError::Io {source: path.display().to_string(), error})
+}
+
+///
+/// Describes where the input of the puzzle is read from, for reporting.
+///
+pub fn source_description(source: &InputSource, layout: &InputLayout, config: &PuzzleConfiguration) -> String{
+    match source{
+        InputSource::PuzzleFile => layout.puzzle_file_path(config).display().to_string(),
+        InputSource::File(path) => path.display().to_string(),
+        InputSource::StandardInput => String::from("standard input"),
     }
 }
 
-fn puzzle_file_path(config: &PuzzleConfiguration) -> PathBuf{
-    let filename = puzzle_file_name(&config);
-    let mut path: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR"))
-                            .parent().unwrap()
-                            .parent().unwrap()
-                            .to_path_buf();
-    path.push("Input");
+///
+/// The puzzle inputs are stored as `<input root>/<year>/Day<day>.txt`.
+///
+pub fn puzzle_file_path(input_root: &Path, config: &PuzzleConfiguration) -> PathBuf{
+    let filename = puzzle_file_name(config);
+    let mut path: PathBuf = input_root.to_path_buf();
+    path.push(config.year.to_string());
     path.push(filename);
     path
 }
 
+///
+/// The input root used when neither the command line, the environment nor a config file sets one.
+///
+pub fn default_input_root() -> PathBuf{
+    repository_root().join("Input")
+}
+
+///
+/// The directory containing the Input folder of the repository the binary was built from.
+///
+pub fn repository_root() -> PathBuf{
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent().unwrap()
+        .parent().unwrap()
+        .to_path_buf()
+}
+
 fn puzzle_file_name(config: &PuzzleConfiguration) -> String{
     let day: &str = &config.day.to_string();
     ["Day", day, ".txt"].join("")
-}
\ No newline at end of file
+}
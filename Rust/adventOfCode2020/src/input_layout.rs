@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ParseError, PuzzleError};
+use crate::input::{self, PuzzleConfiguration};
+
+pub const INPUT_ROOT_VARIABLE: &str = "AOC_INPUT_ROOT";
+pub const INPUT_SET_VARIABLE: &str = "AOC_INPUT_SET";
+pub const CONFIG_FILE_VARIABLE: &str = "AOC_CONFIG";
+pub const ANSWERS_FILE_VARIABLE: &str = "AOC_ANSWERS";
+pub const DEFAULT_CONFIG_FILE_NAME: &str = "advent_of_code.conf";
+pub const DEFAULT_ANSWERS_FILE_NAME: &str = "Answers.txt";
+
+///
+/// Explicit choices about where to read the puzzle inputs from, e.g. from the command line.
+/// Everything left open is resolved from the environment, the config file and the compile-time default.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InputOptions{
+    pub input_root: Option<PathBuf>,
+    pub input_set: Option<String>,
+    pub config_file: Option<PathBuf>,
+}
+
+///
+/// Resolved location of the puzzle inputs: a root directory containing `<year>/Day<day>.txt`
+/// and paths for single days that are stored elsewhere, together with the file of the known answers to them.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputLayout{
+    pub root: PathBuf,
+    pub answers_file: PathBuf,
+    overrides: HashMap<(i32, i32), PathBuf>,
+}
+
+impl InputLayout{
+    ///
+    /// Resolves the layout from the options, the environment variables and the config file.
+    ///
+    pub fn resolve(options: &InputOptions) -> Result<InputLayout, PuzzleError>{
+        let config_file = resolve_config_file(options, |name| env::var(name).ok())?;
+        resolve_layout(options, config_file.as_ref(), |name| env::var(name).ok())
+    }
+
+    pub fn puzzle_file_path(&self, config: &PuzzleConfiguration) -> PathBuf{
+        match self.overrides.get(&(config.year, config.day)){
+            Some(path) => path.to_owned(),
+            None => input::puzzle_file_path(&self.root, config),
+        }
+    }
+}
+
+///
+/// Settings of the config file.
+///
+/// The file consists of `key = value` lines. The lines before the first section make up the default input set,
+/// sections named `[<name>]` start the named input sets. Within a set, `input_root` gives the input root,
+/// `answers_file` the known answers and `<year>/<day>` the input file of a single day. At the top, `input_set` selects the input set used by default.
+/// Relative paths are relative to the directory of the config file. Lines starting with `#` are comments.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InputConfigFile{
+    default_set: Option<String>,
+    sets: HashMap<String, InputSetConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct InputSetConfig{
+    input_root: Option<PathBuf>,
+    answers_file: Option<PathBuf>,
+    overrides: HashMap<(i32, i32), PathBuf>,
+}
+
+const DEFAULT_SET_NAME: &str = "";
+
+impl InputConfigFile{
+    pub fn parse(text: &str, base_directory: &Path) -> Result<InputConfigFile, ParseError>{
+        let mut config_file = InputConfigFile::default();
+        let mut current_set = String::from(DEFAULT_SET_NAME);
+        config_file.sets.insert(current_set.clone(), InputSetConfig::default());
+
+        for (line_index, line) in text.lines().enumerate(){
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#'){
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')){
+                current_set = String::from(section.trim());
+                if current_set.is_empty() || config_file.sets.insert(current_set.clone(), InputSetConfig::default()).is_some(){
+                    return Err(ParseError::at(line_index, 0, &format!("The input set '{}' must have a unique, non-empty name.", current_set)));
+                }
+                continue;
+            }
+
+            let (key, value) = match line.find('='){
+                Some(separator_index) => (line[..separator_index].trim(), line[separator_index + 1..].trim()),
+                None => return Err(ParseError::at(line_index, 0, "Expected a line of the form <key> = <value>.")),
+            };
+            let set_config = config_file.sets.get_mut(&current_set).unwrap();
+            match key{
+                "input_set" if current_set == DEFAULT_SET_NAME => config_file.default_set = Some(String::from(value)),
+                "input_root" => set_config.input_root = Some(base_directory.join(value)),
+                "answers_file" => set_config.answers_file = Some(base_directory.join(value)),
+                day_key => {
+                    let year_and_day = parse_day_key(day_key)
+                        .ok_or_else(|| ParseError::at(line_index, 0, &format!("Unknown setting '{}'.", day_key)))?;
+                    set_config.overrides.insert(year_and_day, base_directory.join(value));
+                },
+            }
+        }
+        Ok(config_file)
+    }
+
+    pub fn load(path: &Path) -> Result<InputConfigFile, PuzzleError>{
+        let text = fs::read_to_string(path)
+            .map_err(|error| PuzzleError::Io {source: path.display().to_string(), error})?;
+        let base_directory = path.parent().unwrap_or_else(|| Path::new(""));
+        InputConfigFile::parse(&text, base_directory)
+            .map_err(|error| PuzzleError::Configuration(format!("{} in {}", error, path.display())))
+    }
+}
+
+fn parse_day_key(key: &str) -> Option<(i32, i32)>{
+    let separator_index = key.find('/')?;
+    let year = key[..separator_index].parse::<i32>().ok()?;
+    let day = key[separator_index + 1..].parse::<i32>().ok()?;
+    Some((year, day))
+}
+
+///
+/// Loads the config file given explicitly or in the environment, or the one in the working directory if it exists.
+///
+fn resolve_config_file(options: &InputOptions, environment: impl Fn(&str) -> Option<String>) -> Result<Option<InputConfigFile>, PuzzleError>{
+    let explicit_path = options.config_file.clone()
+        .or_else(|| environment(CONFIG_FILE_VARIABLE).map(PathBuf::from));
+    match explicit_path{
+        Some(path) => InputConfigFile::load(&path).map(Some),
+        None => {
+            let default_path = PathBuf::from(DEFAULT_CONFIG_FILE_NAME);
+            if default_path.is_file(){
+                InputConfigFile::load(&default_path).map(Some)
+            } else {
+                Ok(None)
+            }
+        },
+    }
+}
+
+///
+/// The input root is taken from the options, then the environment, then the config file
+/// and finally the Input folder of the repository the binary was built from.
+/// Days overridden in the config file are read from their own paths regardless of the input root.
+/// The answers file is taken from the environment, then the config file and otherwise lies next to the input root.
+///
+fn resolve_layout(options: &InputOptions, config_file: Option<&InputConfigFile>, environment: impl Fn(&str) -> Option<String>) -> Result<InputLayout, PuzzleError>{
+    let set_name = options.input_set.clone()
+        .or_else(|| environment(INPUT_SET_VARIABLE))
+        .or_else(|| config_file.and_then(|config_file| config_file.default_set.clone()));
+
+    let empty_set_config = InputSetConfig::default();
+    let set_configs: Vec<&InputSetConfig> = match (config_file, &set_name){
+        (None, None) => vec![],
+        (None, Some(name)) => return Err(PuzzleError::Configuration(format!("The input set '{}' requires a config file.", name))),
+        (Some(config_file), None) => vec![config_file.sets.get(DEFAULT_SET_NAME).unwrap_or(&empty_set_config)],
+        (Some(config_file), Some(name)) => {
+            let named_set = config_file.sets.get(name.as_str())
+                .ok_or_else(|| PuzzleError::Configuration(format!("There is no input set '{}' in the config file.", name)))?;
+            vec![named_set, config_file.sets.get(DEFAULT_SET_NAME).unwrap_or(&empty_set_config)]
+        },
+    };
+
+    let root = options.input_root.clone()
+        .or_else(|| environment(INPUT_ROOT_VARIABLE).map(PathBuf::from))
+        .or_else(|| set_configs.iter().find_map(|set_config| set_config.input_root.clone()))
+        .unwrap_or_else(input::default_input_root);
+    let answers_file = environment(ANSWERS_FILE_VARIABLE).map(PathBuf::from)
+        .or_else(|| set_configs.iter().find_map(|set_config| set_config.answers_file.clone()))
+        .unwrap_or_else(|| default_answers_file(&root));
+
+    let mut overrides = HashMap::new();
+    for set_config in set_configs.iter().rev(){
+        overrides.extend(set_config.overrides.iter().map(|(key, path)| (*key, path.clone())));
+    }
+    Ok(InputLayout {root, answers_file, overrides})
+}
+
+fn default_answers_file(input_root: &Path) -> PathBuf{
+    input_root.parent()
+        .unwrap_or(input_root)
+        .join(DEFAULT_ANSWERS_FILE_NAME)
+}
+
+
+
+#[cfg(test)]
+mod input_layout_tests {
+    use super::*;
+    use rstest::rstest;
+
+    const CONFIG_TEXT: &str = "
+# Personal inputs unless another set is selected.
+input_set = personal
+input_root = shared
+
+[personal]
+input_root = /data/personal
+2020/18 = alternative/Day18.txt
+
+[example]
+input_root = /data/example
+answers_file = example/Answers.txt
+";
+
+    fn config_file() -> InputConfigFile{
+        InputConfigFile::parse(CONFIG_TEXT, Path::new("/config")).unwrap()
+    }
+
+    fn environment_with(variables: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String>{
+        move |name| variables.iter()
+            .find(|(variable, _)| *variable == name)
+            .map(|(_, value)| String::from(*value))
+    }
+
+    #[rstest]
+    #[case(Some("/cli"), &[(INPUT_ROOT_VARIABLE, "/env")], "/cli")]
+    #[case(None, &[(INPUT_ROOT_VARIABLE, "/env")], "/env")]
+    #[case(None, &[], "/data/personal")]
+    #[case(None, &[(INPUT_SET_VARIABLE, "example")], "/data/example")]
+    fn input_root_precedence(#[case] cli_root: Option<&str>, #[case] variables: &'static [(&'static str, &'static str)], #[case] expected_root: &str) {
+        let options = InputOptions {input_root: cli_root.map(PathBuf::from), ..InputOptions::default()};
+        let layout = resolve_layout(&options, Some(&config_file()), environment_with(variables)).unwrap();
+        assert_eq!(layout.root, PathBuf::from(expected_root));
+    }
+
+    #[rstest]
+    #[case(&[(ANSWERS_FILE_VARIABLE, "/env/Answers.txt")], "/env/Answers.txt")]
+    #[case(&[(INPUT_SET_VARIABLE, "example")], "/config/example/Answers.txt")]
+    #[case(&[], "/data/Answers.txt")]
+    fn answers_file_precedence(#[case] variables: &'static [(&'static str, &'static str)], #[case] expected_answers_file: &str) {
+        let layout = resolve_layout(&InputOptions::default(), Some(&config_file()), environment_with(variables)).unwrap();
+        assert_eq!(layout.answers_file, PathBuf::from(expected_answers_file));
+    }
+
+    #[test]
+    fn answers_next_to_the_input_root() {
+        let options = InputOptions {input_root: Some(PathBuf::from("/copied/release/Input")), ..InputOptions::default()};
+        let layout = resolve_layout(&options, None, environment_with(&[])).unwrap();
+        assert_eq!(layout.answers_file, PathBuf::from("/copied/release/Answers.txt"));
+    }
+
+    #[test]
+    fn default_root_without_configuration() {
+        let layout = resolve_layout(&InputOptions::default(), None, environment_with(&[])).unwrap();
+        assert_eq!(layout.root, input::default_input_root());
+    }
+
+    #[test]
+    fn overridden_days_use_their_own_path() {
+        let layout = resolve_layout(&InputOptions::default(), Some(&config_file()), environment_with(&[])).unwrap();
+        let overridden_path = layout.puzzle_file_path(&PuzzleConfiguration {year: 2020, day: 18, part: 1});
+        let regular_path = layout.puzzle_file_path(&PuzzleConfiguration {year: 2020, day: 17, part: 1});
+        assert_eq!(overridden_path, PathBuf::from("/config/alternative/Day18.txt"));
+        assert_eq!(regular_path, PathBuf::from("/data/personal/2020/Day17.txt"));
+    }
+
+    #[test]
+    fn explicit_input_set_is_used() {
+        let options = InputOptions {input_set: Some(String::from("example")), ..InputOptions::default()};
+        let layout = resolve_layout(&options, Some(&config_file()), environment_with(&[(INPUT_SET_VARIABLE, "personal")])).unwrap();
+        assert_eq!(layout.root, PathBuf::from("/data/example"));
+    }
+
+    #[rstest]
+    #[case(Some("unknown"), true)]
+    #[case(None, false)]
+    fn unknown_input_sets_are_rejected(#[case] input_set: Option<&str>, #[case] expected_error: bool) {
+        let options = InputOptions {input_set: input_set.map(String::from), ..InputOptions::default()};
+        let result = resolve_layout(&options, Some(&config_file()), environment_with(&[]));
+        assert_eq!(result.is_err(), expected_error);
+    }
+
+    #[rstest]
+    #[case("input_root /data", 1)]
+    #[case("input_root = /data\n[]", 2)]
+    #[case("[example]\n[example]", 2)]
+    #[case("2020/x = Day1.txt", 1)]
+    fn invalid_config_files_are_reported_with_their_line(#[case] text: &str, #[case] expected_line: usize) {
+        let error = InputConfigFile::parse(text, Path::new("")).unwrap_err();
+        assert_eq!(error.line, expected_line);
+    }
+}
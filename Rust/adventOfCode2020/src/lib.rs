@@ -0,0 +1,31 @@
+//!
+//! Solutions to the Advent of Code puzzles together with the building blocks they share:
+//! points and vectors in `space`, grids in `grid`, cellular automata in `game_of_life`,
+//! number theory in `algebra` and the parsers in `formula_parsing` and `parser_generator`.
+//! The modules from `day` on make up the command line runner built on top of them.
+//!
+
+pub mod util;
+pub mod algebra;
+pub mod space;
+pub mod grid;
+pub mod game_of_life;
+pub mod formula_parsing;
+pub mod parser_generator;
+
+pub mod day;
+pub mod error;
+pub mod input;
+pub mod input_layout;
+pub mod cli;
+pub mod report;
+pub mod bench;
+pub mod answers;
+pub mod thread_pool;
+pub mod runner;
+
+#[macro_use] extern crate lazy_static;
+extern crate regex;
+extern crate num;
+
+#[cfg(test)] extern crate rstest;
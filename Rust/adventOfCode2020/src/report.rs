@@ -0,0 +1,170 @@
+use crate::day::TimedAnswer;
+use crate::error::PuzzleError;
+use crate::input::PuzzleConfiguration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat{
+    Text,
+    Json,
+    Csv,
+}
+
+pub const CSV_HEADER: &str = "year,day,part,variant,status,answer,parse_time_ns,solve_time_ns,input,error";
+
+///
+/// Outcome of running a single part of a puzzle, as reported by the runner.
+///
+#[derive(Debug)]
+pub struct PuzzleRecord{
+    pub config: PuzzleConfiguration,
+    pub variant: String,
+    pub input: String,
+    pub outcome: Result<TimedAnswer, PuzzleError>,
+}
+
+impl PuzzleRecord{
+    pub fn is_success(&self) -> bool{
+        self.outcome.is_ok()
+    }
+
+    pub fn status(&self) -> &'static str{
+        match &self.outcome{
+            Ok(_) => "ok",
+            Err(PuzzleError::UnknownDay{..}) => "unknown_day",
+            Err(PuzzleError::UnknownVariant{..}) => "unknown_variant",
+            Err(PuzzleError::InvalidPart(_)) => "invalid_part",
+            Err(PuzzleError::Io{..}) => "io_error",
+            Err(PuzzleError::Parse(_)) => "parse_error",
+            Err(PuzzleError::Unsolvable(_)) => "unsolvable",
+            Err(PuzzleError::Configuration(_)) => "configuration_error",
+        }
+    }
+
+    pub fn to_json(&self) -> String{
+        let (answer, parse_time, solve_time, error) = match &self.outcome{
+            Ok(timed_answer) => (
+                json_string(&timed_answer.answer),
+                timed_answer.parse_time.as_nanos().to_string(),
+                timed_answer.solve_time.as_nanos().to_string(),
+                String::from("null")),
+            Err(error) => (
+                String::from("null"),
+                String::from("null"),
+                String::from("null"),
+                json_string(&error.to_string())),
+        };
+        format!("{{\"year\":{},\"day\":{},\"part\":{},\"variant\":{},\"status\":{},\"answer\":{},\"parse_time_ns\":{},\"solve_time_ns\":{},\"input\":{},\"error\":{}}}",
+            self.config.year,
+            self.config.day,
+            self.config.part,
+            json_string(&self.variant),
+            json_string(self.status()),
+            answer,
+            parse_time,
+            solve_time,
+            json_string(&self.input),
+            error)
+    }
+
+    ///
+    /// Formats the record as a CSV row matching `CSV_HEADER`.
+    ///
+    pub fn to_csv(&self) -> String{
+        let (answer, parse_time, solve_time, error) = match &self.outcome{
+            Ok(timed_answer) => (
+                csv_field(&timed_answer.answer),
+                timed_answer.parse_time.as_nanos().to_string(),
+                timed_answer.solve_time.as_nanos().to_string(),
+                String::new()),
+            Err(error) => (
+                String::new(),
+                String::new(),
+                String::new(),
+                csv_field(&error.to_string())),
+        };
+        [
+            self.config.year.to_string(),
+            self.config.day.to_string(),
+            self.config.part.to_string(),
+            csv_field(&self.variant),
+            String::from(self.status()),
+            answer,
+            parse_time,
+            solve_time,
+            csv_field(&self.input),
+            error,
+        ].join(",")
+    }
+}
+
+fn json_string(text: &str) -> String{
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for character in text.chars(){
+        match character{
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            control if control.is_control() => escaped.push_str(&format!("\\u{:04x}", control as u32)),
+            other => escaped.push(other),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(text: &str) -> String{
+    if text.contains(&[',', '"', '\n', '\r'][..]){
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        String::from(text)
+    }
+}
+
+
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::error::ParseError;
+
+    fn solved_record() -> PuzzleRecord{
+        let timed_answer = TimedAnswer {
+            answer: String::from("42"),
+            parse_time: Duration::from_nanos(1500),
+            solve_time: Duration::from_micros(2),
+        };
+        PuzzleRecord {config: PuzzleConfiguration {year: 2020, day: 3, part: 2}, variant: String::from("v1"), input: String::from("Input/2020/Day3.txt"), outcome: Ok(timed_answer)}
+    }
+
+    fn failed_record() -> PuzzleRecord{
+        let error = PuzzleError::Parse(ParseError::at(4, 0, "Unexpected \"x\", expected a number."));
+        PuzzleRecord {config: PuzzleConfiguration {year: 2020, day: 7, part: 1}, variant: String::from("v2"), input: String::from("C:\\puzzles\\day7.txt"), outcome: Err(error)}
+    }
+
+    #[test]
+    fn solved_record_as_json() {
+        let expected = r#"{"year":2020,"day":3,"part":2,"variant":"v1","status":"ok","answer":"42","parse_time_ns":1500,"solve_time_ns":2000,"input":"Input/2020/Day3.txt","error":null}"#;
+        assert_eq!(solved_record().to_json(), expected);
+    }
+
+    #[test]
+    fn failed_record_as_json() {
+        let expected = r#"{"year":2020,"day":7,"part":1,"variant":"v2","status":"parse_error","answer":null,"parse_time_ns":null,"solve_time_ns":null,"input":"C:\\puzzles\\day7.txt","error":"Invalid input in line 5, column 1: Unexpected \"x\", expected a number."}"#;
+        assert_eq!(failed_record().to_json(), expected);
+    }
+
+    #[test]
+    fn solved_record_as_csv() {
+        assert_eq!(solved_record().to_csv(), "2020,3,2,v1,ok,42,1500,2000,Input/2020/Day3.txt,");
+    }
+
+    #[test]
+    fn failed_record_as_csv() {
+        let expected = r#"2020,7,1,v2,parse_error,,,,C:\puzzles\day7.txt,"Invalid input in line 5, column 1: Unexpected ""x"", expected a number.""#;
+        assert_eq!(failed_record().to_csv(), expected);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::answers::{Answers, Verdict};
+use crate::bench::{self, Baseline, BenchResult};
+use crate::cli::{BenchOptions, ListOptions, PuzzleSelection, RunOptions, VerifyOptions};
+use crate::day::{self, DaySolver, TimedAnswer};
+use crate::error::PuzzleError;
+use crate::input::{self, PuzzleConfiguration};
+use crate::input_layout::InputLayout;
+use crate::report::{self, OutputFormat, PuzzleRecord};
+use crate::thread_pool;
+
+fn resolve_input_layout(puzzles: &PuzzleSelection) -> Option<InputLayout>{
+    match InputLayout::resolve(&puzzles.input){
+        Ok(layout) => Some(layout),
+        Err(error) => {
+            eprintln!("{}", error);
+            None
+        }
+    }
+}
+
+///
+/// Runs all configured puzzles on up to `options.jobs` threads and reports whether all of them could be solved.
+/// The inputs are read up front, so that each day's input is read only once.
+///
+pub fn run_puzzles(options: &RunOptions) -> bool{
+    let layout = match resolve_input_layout(&options.puzzles){
+        Some(layout) => layout,
+        None => return false,
+    };
+    if options.format == OutputFormat::Csv{
+        println!("{}", report::CSV_HEADER);
+    }
+    let mut puzzles = vec![];
+    let mut loaded_input: Option<(i32, Result<String, PuzzleError>)> = None;
+    for config in options.puzzles.puzzle_configurations(){
+        let is_loaded = matches!(loaded_input, Some((day, _)) if day == config.day);
+        if !is_loaded{
+            let maybe_text = input::input_from_source(&options.puzzles.input_source, &layout, &config);
+            loaded_input = Some((config.day, maybe_text));
+        }
+        let variant = options.puzzles.variant(config.day).map(String::from);
+        let maybe_text = match &loaded_input{
+            Some((_, maybe_text)) => maybe_text.clone(),
+            None => unreachable!("The input has been loaded above."),
+        };
+        puzzles.push((config, variant, maybe_text));
+    }
+
+    let mut all_succeeded = true;
+    let solve = |(config, variant, maybe_text): (PuzzleConfiguration, Option<String>, Result<String, PuzzleError>)| {
+        let outcome = maybe_text.and_then(|text| puzzle_output(&config, variant.as_deref(), text));
+        (config, outcome)
+    };
+    thread_pool::run_in_order(puzzles, options.jobs, solve, |(config, outcome)| {
+        let variant = variant_label(&config, &options.puzzles);
+        let input = input::source_description(&options.puzzles.input_source, &layout, &config);
+        let record = PuzzleRecord {config, variant, input, outcome};
+        all_succeeded &= record.is_success();
+        print_record(&record, options.format);
+    });
+    all_succeeded
+}
+
+fn print_record(record: &PuzzleRecord, format: OutputFormat){
+    match format{
+        OutputFormat::Text => match &record.outcome{
+            Ok(timed_answer) => {
+                println!("Day {} part {}: {}", record.config.day, record.config.part, timed_answer.answer);
+                println!("{:?}", timed_answer.parse_time + timed_answer.solve_time);
+            },
+            Err(error) => eprintln!("Day {} part {}: {}", record.config.day, record.config.part, error),
+        },
+        OutputFormat::Json => println!("{}", record.to_json()),
+        OutputFormat::Csv => println!("{}", record.to_csv()),
+    }
+}
+
+///
+/// Benchmarks all configured puzzles and reports whether all of them could be solved without regressions.
+///
+pub fn bench_puzzles(options: &BenchOptions) -> bool{
+    let layout = match resolve_input_layout(&options.puzzles){
+        Some(layout) => layout,
+        None => return false,
+    };
+    let baseline = match &options.baseline{
+        None => None,
+        Some(path) => match Baseline::load(path){
+            Ok(baseline) => Some(baseline),
+            Err(error) => {
+                eprintln!("{}", error);
+                return false;
+            }
+        },
+    };
+
+    let mut all_succeeded = true;
+    let mut results: Vec<BenchResult> = vec![];
+    let mut loaded_input: Option<(i32, Result<String, PuzzleError>)> = None;
+    for config in options.puzzles.puzzle_configurations(){
+        let is_loaded = matches!(loaded_input, Some((day, _)) if day == config.day);
+        if !is_loaded{
+            let maybe_text = input::input_from_source(&options.puzzles.input_source, &layout, &config);
+            loaded_input = Some((config.day, maybe_text));
+        }
+        let outcome = match &loaded_input{
+            Some((_, Ok(text))) => day::find_registration(config.year, config.day, options.puzzles.variant(config.day))
+                .and_then(|registration| bench::benchmark(registration.create_solver().as_ref(), &config, text, options.warmup_runs, options.runs)),
+            Some((_, Err(error))) => Err(error.clone()),
+            None => unreachable!("The input has been loaded above."),
+        };
+
+        let result = match outcome{
+            Ok(result) => result,
+            Err(error) => {
+                eprintln!("Day {} part {}: {}", config.day, config.part, error);
+                all_succeeded = false;
+                continue;
+            }
+        };
+        println!("Day {} part {} {} ({} runs after {} warmup runs)", config.day, config.part, variant_label(&config, &options.puzzles), options.runs, options.warmup_runs);
+        println!("    parse: {}", result.parse);
+        println!("    solve: {}", result.solve);
+        if let Some(baseline_entry) = baseline.as_ref().and_then(|baseline| baseline.entry(&config)){
+            for regression in bench::regressions(&result, baseline_entry, options.regression_threshold_percent){
+                println!("    REGRESSION: {}", regression);
+                all_succeeded = false;
+            }
+        }
+        results.push(result);
+    }
+
+    if let Some(path) = &options.save_baseline{
+        if let Err(error) = Baseline::from_results(&results).save(path){
+            eprintln!("{}", error);
+            return false;
+        }
+    }
+    all_succeeded
+}
+
+///
+/// Compares the answers of all configured puzzles with the known answers and reports whether none of them is wrong.
+///
+pub fn verify_puzzles(options: &VerifyOptions) -> bool{
+    let layout = match resolve_input_layout(&options.puzzles){
+        Some(layout) => layout,
+        None => return false,
+    };
+    let answers_file = options.answers_file.clone()
+        .unwrap_or_else(|| layout.answers_file.clone());
+    let answers = match Answers::load(&answers_file){
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("{}", error);
+            return false;
+        }
+    };
+
+    let mut verdict_counts = [0, 0, 0];
+    for config in options.puzzles.puzzle_configurations(){
+        let answer = input::puzzle_input(&layout, &config)
+            .and_then(|text| solve_puzzle(&config, options.puzzles.variant(config.day), text));
+        let expected_answer = answers.answer(&config);
+        let verdict = Verdict::of(expected_answer, &answer);
+        let details = match (&verdict, expected_answer, &answer){
+            (Verdict::Pass, _, _) => String::new(),
+            (_, Some(expected), Ok(actual)) => format!(" (expected {}, got {})", expected, actual),
+            (_, Some(expected), Err(error)) => format!(" (expected {}, got error: {})", expected, error),
+            (_, None, Ok(actual)) => format!(" (got {})", actual),
+            (_, None, Err(error)) => format!(" (got error: {})", error),
+        };
+        println!("Day {} part {}: {}{}", config.day, config.part, verdict, details);
+        match verdict{
+            Verdict::Pass => verdict_counts[0] += 1,
+            Verdict::Fail => verdict_counts[1] += 1,
+            Verdict::Missing => verdict_counts[2] += 1,
+        }
+    }
+    println!("{} passed, {} failed, {} missing", verdict_counts[0], verdict_counts[1], verdict_counts[2]);
+    verdict_counts[1] == 0
+}
+
+///
+/// Lists the registered solvers for the days, marking the default variants.
+///
+pub fn list_solvers(options: &ListOptions){
+    let years = match options.year{
+        Some(year) => vec![year],
+        None => day::years(),
+    };
+    println!("Year  Day  Variant  Name");
+    for &year in &years{
+        for &day in &options.days{
+            for registration in day::variants(year, day){
+                let default_marker = if registration.is_default {"*"} else {""};
+                println!("{:<4}  {:>3}  {:<7}  {}", registration.year, registration.day, format!("{}{}", registration.variant, default_marker), registration.name);
+            }
+        }
+    }
+    println!("* default variant");
+}
+
+///
+/// The label of the variant selected for the day, or of the default variant if none is selected.
+///
+fn variant_label(config: &PuzzleConfiguration, puzzles: &PuzzleSelection) -> String{
+    match puzzles.variant(config.day){
+        Some(variant) => String::from(variant),
+        None => day::find_registration(config.year, config.day, None)
+            .map(|registration| String::from(registration.variant))
+            .unwrap_or_default(),
+    }
+}
+
+fn solve_puzzle(config: &PuzzleConfiguration, variant: Option<&str>, input: String) -> Result<String, PuzzleError>{
+    puzzle_output(config, variant, input)
+        .map(|timed_answer| timed_answer.answer)
+}
+
+fn puzzle_output(config: &PuzzleConfiguration, variant: Option<&str>, input: String) -> Result<TimedAnswer, PuzzleError>{
+    let solver = day::find_registration(config.year, config.day, variant)?
+        .create_solver();
+    solve_part(solver.as_ref(), config.part, input)
+}
+
+///
+/// Solves the part of the puzzle with the solver.
+///
+pub fn solve_part(solver: &dyn DaySolver, part: i32, input: String) -> Result<TimedAnswer, PuzzleError>{
+    match part{
+        1 => solver.solve_part1_timed(input),
+        2 => solver.solve_part2_timed(input),
+        part => Err(PuzzleError::InvalidPart(part))
+    }
+}
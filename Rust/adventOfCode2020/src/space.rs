@@ -1,9 +1,20 @@
 use std::ops::{Add,Mul,Sub,Neg,Div,Index};
-use std::convert::TryFrom;
-use std::num::TryFromIntError;
 use std::iter::IntoIterator;
 use std::slice::Iter;
-use crate::util;
+mod hex;
+
+mod matrix;
+mod affine;
+mod symmetry;
+mod bounding_box;
+mod neighbourhood;
+
+pub use matrix::Matrix;
+pub use affine::{AffineTransform, Transformable};
+pub use symmetry::{SignedPermutation, D4};
+pub use bounding_box::{BoundingBox, RowMajorPoints, MortonPoints};
+pub use neighbourhood::{Neighbourhood, Reach};
+pub use hex::{Axial, Cube, HexDirection, HexPath, ParseHexDirectionError};
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub struct Vector<T, const N: usize>{
@@ -167,20 +178,12 @@ impl<'a, T: Copy, const N:usize> IntoIterator for &'a Point<T, N>{
 }
 
 
-impl<T: Neg<Output = T> + Default + Copy> Vector<T, 2>{
+impl<T: num::Signed + Copy> Vector<T, 2>{
+    ///
+    /// Rotates the vector by a multiple of 90 degrees in counter-clockwise direction.
+    ///
     pub fn rotate(self, angle: i64) -> Vector<T, 2>{
-        let rotation_steps: i64 = util::modulo(angle, 360) / 90;
-        match rotation_steps{
-            0 => self,
-            1 => Vector{
-                arr: [-self[1], self[0]],
-            },
-            2 => -self,
-            3 => Vector{
-                arr: [self[1], -self[0]],
-            },
-            _ => unreachable!(), //This cannot happen because of how modulo works.
-        }
+        Matrix::quarter_turn_rotation(angle) * self
     }
 }
 
@@ -192,6 +195,41 @@ impl<T: num::Integer + Div<Output = T> + Default + Copy> Vector<T, 2>{
 }
 
 
+impl<T: num::Num + Copy, const N: usize> Vector<T, N>{
+    pub fn dot(self, other: Vector<T, N>) -> T{
+        self.into_iter()
+            .zip(&other)
+            .fold(T::zero(), |sum, (a, b)| sum + *a * *b)
+    }
+
+    pub fn squared_l2_norm(self) -> T{
+        self.dot(self)
+    }
+}
+
+impl<T: num::Num + num::ToPrimitive + Copy, const N: usize> Vector<T, N>{
+    ///
+    /// The euclidean length, or `NaN` if the coordinates cannot be represented as floating point numbers.
+    ///
+    pub fn l2_norm(self) -> f64{
+        self.squared_l2_norm()
+            .to_f64()
+            .map_or(f64::NAN, f64::sqrt)
+    }
+}
+
+impl<T: num::Num + Copy> Vector<T, 3>{
+    pub fn cross(self, other: Vector<T, 3>) -> Vector<T, 3>{
+        Vector {
+            arr: [
+                self[1] * other[2] - self[2] * other[1],
+                self[2] * other[0] - self[0] * other[2],
+                self[0] * other[1] - self[1] * other[0],
+            ],
+        }
+    }
+}
+
 impl<T: num::Signed + Copy, const N: usize> Vector<T, N>{
     pub fn l1_norm(self) -> T{
         self.into_iter()
@@ -200,38 +238,39 @@ impl<T: num::Signed + Copy, const N: usize> Vector<T, N>{
     }
 }
 
+impl<T: num::Signed + PartialOrd + Copy, const N: usize> Vector<T, N>{
+    pub fn linf_norm(self) -> T{
+        self.into_iter()
+            .map(|coordinate| coordinate.abs())
+            .fold(T::zero(), |maximum, item| if item > maximum { item } else { maximum })
+    }
+}
+
 pub fn manhattan_metric<T: num::Signed + Default + Copy, const N:usize>(a: Point<T, N>, b: Point<T, N>) -> T{
     (a - b).l1_norm()
 }
 
-/*
-pub fn neighbour_offsets_unidirectional<T: num::One + num::Zero + num::Num + Neg<Output = T> + Copy, const N: usize>() -> Vec<Vector<T, N>>{
-    let mut offsets: Vec<Vector<T, N>> = vec![];
-    for ind in 0..N{
-        for x in [T::one(), -(T::one())].iter(){
-            let mut arr = [T::zero(); N];
-            arr[ind] = x.to_owned();
-            let offset = Vector {arr};
-            offsets.push(offset);
-        }
+
+
+#[cfg(test)]
+mod space_tests {
+    use super::*;
+
+    #[test]
+    fn products() {
+        let a = Vector::new([1, 2, 3]);
+        let b = Vector::new([4, -5, 6]);
+        assert_eq!(a.dot(b), 12);
+        assert_eq!(a.cross(b), Vector::new([27, 6, -13]));
+        assert_eq!(a.cross(b).dot(a), 0);
     }
-    offsets
-}
-*/
 
-pub fn neighbour_offsets_full<T: num::One + num::Zero + num::Num + Neg<Output = T> + Copy, const N: usize>() -> Result<Vec<Vector<T, N>>, TryFromIntError>{
-    let n_u32 = u32::try_from(N)?;
-    let mut offsets: Vec<Vector<T, N>> = vec![];
-    let coordinate_values = [T::one(), -(T::one()), T::zero()];
-    for offset_index in 0..(3usize.pow(n_u32)-1){
-        let mut arr = [T::zero(); N];
-        let mut normalized_offset = offset_index;
-        for vector_coordinate in arr.iter_mut(){
-            *vector_coordinate = coordinate_values[normalized_offset % 3];
-            normalized_offset /= 3;
-        }
-        let offset = Vector {arr};
-        offsets.push(offset);
+    #[test]
+    fn norms() {
+        let vector = Vector::new([3, -4]);
+        assert_eq!(vector.l1_norm(), 7);
+        assert_eq!(vector.squared_l2_norm(), 25);
+        assert_eq!(vector.l2_norm(), 5.0);
+        assert_eq!(vector.linf_norm(), 4);
     }
-    Ok(offsets)
-}
\ No newline at end of file
+}
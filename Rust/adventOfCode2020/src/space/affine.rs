@@ -0,0 +1,86 @@
+use super::{Matrix, Point, Vector};
+
+///
+/// Affine map `x -> linear * x + translation`. Points are moved by the translation, vectors are not.
+///
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct AffineTransform<T, const N: usize>{
+    pub linear: Matrix<T, N, N>,
+    pub translation: Vector<T, N>,
+}
+
+impl<T: num::Num + Default + Copy, const N: usize> AffineTransform<T, N>{
+    pub fn new(linear: Matrix<T, N, N>, translation: Vector<T, N>) -> AffineTransform<T, N>{
+        AffineTransform {linear, translation}
+    }
+
+    pub fn identity() -> AffineTransform<T, N>{
+        AffineTransform::linear(Matrix::identity())
+    }
+
+    pub fn linear(linear: Matrix<T, N, N>) -> AffineTransform<T, N>{
+        AffineTransform {linear, translation: Vector::new([T::zero(); N])}
+    }
+
+    pub fn translation(translation: Vector<T, N>) -> AffineTransform<T, N>{
+        AffineTransform {linear: Matrix::identity(), translation}
+    }
+
+    ///
+    /// The transform applying `self` first and `other` afterwards.
+    ///
+    pub fn then(&self, other: &AffineTransform<T, N>) -> AffineTransform<T, N>{
+        AffineTransform {
+            linear: other.linear * self.linear,
+            translation: other.linear * self.translation + other.translation,
+        }
+    }
+
+    pub fn apply<X: Transformable<T, N>>(&self, item: X) -> X{
+        item.transformed(self)
+    }
+}
+
+///
+/// Items that affine transforms can be applied to.
+///
+pub trait Transformable<T, const N: usize>{
+    fn transformed(self, transform: &AffineTransform<T, N>) -> Self;
+}
+
+impl<T: num::Num + Copy, const N: usize> Transformable<T, N> for Vector<T, N>{
+    fn transformed(self, transform: &AffineTransform<T, N>) -> Self{
+        transform.linear * self
+    }
+}
+
+impl<T: num::Num + Default + Copy, const N: usize> Transformable<T, N> for Point<T, N>{
+    fn transformed(self, transform: &AffineTransform<T, N>) -> Self{
+        let origin = Point::new([T::zero(); N]);
+        origin + (transform.linear * (self - origin) + transform.translation)
+    }
+}
+
+
+
+#[cfg(test)]
+mod affine_tests {
+    use super::*;
+
+    #[test]
+    fn translations_move_points_but_not_vectors() {
+        let transform = AffineTransform::translation(Vector::new([3, -1]));
+        assert_eq!(transform.apply(Point::new([1, 1])), Point::new([4, 0]));
+        assert_eq!(transform.apply(Vector::new([1, 1])), Vector::new([1, 1]));
+    }
+
+    #[test]
+    fn composition_applies_the_first_transform_first() {
+        let rotation = AffineTransform::linear(Matrix::quarter_turn_rotation(90));
+        let translation = AffineTransform::translation(Vector::new([10, 0]));
+        let point = Point::new([1, 2]);
+        assert_eq!(rotation.then(&translation).apply(point), Point::new([8, 1]));
+        assert_eq!(translation.then(&rotation).apply(point), Point::new([-2, 11]));
+        assert_eq!(rotation.then(&translation).apply(point), translation.apply(rotation.apply(point)));
+    }
+}
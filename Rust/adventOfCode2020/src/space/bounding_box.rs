@@ -0,0 +1,291 @@
+use super::Point;
+
+///
+/// Axis-aligned box `[lower_bound[0], upper_bound[0]) x ... x [lower_bound[N-1], upper_bound[N-1])`.
+/// The box is empty if the lower bound is not below the upper bound along any axis.
+///
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct BoundingBox<T, const N: usize>{
+    lower_bound: Point<T, N>,
+    upper_bound: Point<T, N>,
+}
+
+impl<T: Copy, const N: usize> BoundingBox<T, N>{
+    pub fn new(lower_bound: Point<T, N>, upper_bound: Point<T, N>) -> BoundingBox<T, N>{
+        BoundingBox {lower_bound, upper_bound}
+    }
+
+    ///
+    /// The smallest point in the box, if it is not empty.
+    ///
+    pub fn lower_bound(&self) -> Point<T, N>{
+        self.lower_bound
+    }
+
+    ///
+    /// The exclusive upper bound of the box.
+    ///
+    pub fn upper_bound(&self) -> Point<T, N>{
+        self.upper_bound
+    }
+}
+
+impl<T: num::PrimInt + Default, const N: usize> BoundingBox<T, N>{
+    ///
+    /// The smallest box containing all the points, or `None` if there are no points.
+    ///
+    pub fn from_points(points: impl IntoIterator<Item = Point<T, N>>) -> Option<BoundingBox<T, N>>{
+        let mut points = points.into_iter();
+        let first_point = points.next()?;
+        let mut bounding_box = BoundingBox::new(first_point, first_point + unit_vector());
+        for point in points{
+            bounding_box.include(point);
+        }
+        Some(bounding_box)
+    }
+
+    pub fn is_empty(&self) -> bool{
+        (0..N).any(|axis| self.lower_bound[axis] >= self.upper_bound[axis])
+    }
+
+    pub fn contains(&self, point: &Point<T, N>) -> bool{
+        (0..N).all(|axis| self.lower_bound[axis] <= point[axis] && point[axis] < self.upper_bound[axis])
+    }
+
+    ///
+    /// Grows the box just enough to contain the point.
+    ///
+    pub fn include(&mut self, point: Point<T, N>){
+        if self.is_empty(){
+            *self = BoundingBox::new(point, point + unit_vector());
+            return;
+        }
+        let mut lower_bound = [T::default(); N];
+        let mut upper_bound = [T::default(); N];
+        for axis in 0..N{
+            lower_bound[axis] = self.lower_bound[axis].min(point[axis]);
+            upper_bound[axis] = self.upper_bound[axis].max(point[axis] + T::one());
+        }
+        *self = BoundingBox::new(Point::new(lower_bound), Point::new(upper_bound));
+    }
+
+    ///
+    /// The box grown by `margin` on every side.
+    ///
+    pub fn expand(&self, margin: T) -> BoundingBox<T, N>{
+        let margin_vector = super::Vector::new([margin; N]);
+        BoundingBox::new(self.lower_bound - margin_vector, self.upper_bound + margin_vector)
+    }
+
+    ///
+    /// The points contained in both boxes, or `None` if there are no such points.
+    ///
+    pub fn intersect(&self, other: &BoundingBox<T, N>) -> Option<BoundingBox<T, N>>{
+        let mut lower_bound = [T::default(); N];
+        let mut upper_bound = [T::default(); N];
+        for axis in 0..N{
+            lower_bound[axis] = self.lower_bound[axis].max(other.lower_bound[axis]);
+            upper_bound[axis] = self.upper_bound[axis].min(other.upper_bound[axis]);
+        }
+        let intersection = BoundingBox::new(Point::new(lower_bound), Point::new(upper_bound));
+        if intersection.is_empty() { None } else { Some(intersection) }
+    }
+
+    ///
+    /// The smallest box containing both boxes.
+    ///
+    pub fn union(&self, other: &BoundingBox<T, N>) -> BoundingBox<T, N>{
+        if self.is_empty(){
+            return *other;
+        }
+        if other.is_empty(){
+            return *self;
+        }
+        let mut lower_bound = [T::default(); N];
+        let mut upper_bound = [T::default(); N];
+        for axis in 0..N{
+            lower_bound[axis] = self.lower_bound[axis].min(other.lower_bound[axis]);
+            upper_bound[axis] = self.upper_bound[axis].max(other.upper_bound[axis]);
+        }
+        BoundingBox::new(Point::new(lower_bound), Point::new(upper_bound))
+    }
+
+    ///
+    /// The number of points along each axis.
+    ///
+    pub fn size(&self) -> [T; N]{
+        let mut size = [T::zero(); N];
+        for (axis, extent) in size.iter_mut().enumerate(){
+            if self.lower_bound[axis] < self.upper_bound[axis]{
+                *extent = self.upper_bound[axis] - self.lower_bound[axis];
+            }
+        }
+        size
+    }
+
+    ///
+    /// The number of points in the box.
+    ///
+    pub fn volume(&self) -> T{
+        self.size().iter()
+            .fold(T::one(), |volume, extent| volume * *extent)
+    }
+
+    ///
+    /// Iterates over the points in row-major order, i.e. the first coordinate changes fastest.
+    ///
+    pub fn points(&self) -> RowMajorPoints<T, N>{
+        let next_point = if self.is_empty() { None } else { Some(self.lower_bound) };
+        RowMajorPoints {bounding_box: *self, next_point}
+    }
+
+    ///
+    /// Iterates over the points in Morton order, i.e. along the Z-order curve that interleaves the bits of the
+    /// offsets from the lower bound, with the bits of the first coordinate being the least significant.
+    /// Points that are close in this order tend to be close in space, too.
+    ///
+    pub fn morton_points(&self) -> MortonPoints<T, N>{
+        let box_size = self.size();
+        let mut size = [0; N];
+        for (axis, extent) in size.iter_mut().enumerate(){
+            *extent = box_size[axis].to_usize()
+                .expect("The extent of a non-negative box fits into a usize.");
+        }
+        let pending_cells = if self.is_empty(){
+            vec![]
+        } else {
+            let largest_extent = size.iter().copied().max().unwrap_or(1);
+            vec![([0; N], largest_extent.next_power_of_two().trailing_zeros())]
+        };
+        MortonPoints {lower_bound: self.lower_bound, size, pending_cells}
+    }
+}
+
+fn unit_vector<T: num::PrimInt, const N: usize>() -> super::Vector<T, N>{
+    super::Vector::new([T::one(); N])
+}
+
+pub struct RowMajorPoints<T, const N: usize>{
+    bounding_box: BoundingBox<T, N>,
+    next_point: Option<Point<T, N>>,
+}
+
+impl<T: num::PrimInt + Default, const N: usize> Iterator for RowMajorPoints<T, N>{
+    type Item = Point<T, N>;
+
+    fn next(&mut self) -> Option<Self::Item>{
+        let current_point = self.next_point?;
+        let mut arr = current_point.arr;
+        self.next_point = None;
+        for axis in 0..N{
+            arr[axis] = arr[axis] + T::one();
+            if arr[axis] < self.bounding_box.upper_bound[axis]{
+                self.next_point = Some(Point::new(arr));
+                break;
+            }
+            arr[axis] = self.bounding_box.lower_bound[axis];
+        }
+        Some(current_point)
+    }
+}
+
+pub struct MortonPoints<T, const N: usize>{
+    lower_bound: Point<T, N>,
+    size: [usize; N],
+    //Cubes with side length 2^level given by their offset from the lower bound, the next one to visit last.
+    pending_cells: Vec<([usize; N], u32)>,
+}
+
+impl<T: num::PrimInt + Default, const N: usize> Iterator for MortonPoints<T, N>{
+    type Item = Point<T, N>;
+
+    fn next(&mut self) -> Option<Self::Item>{
+        while let Some((offset, level)) = self.pending_cells.pop(){
+            if (0..N).any(|axis| offset[axis] >= self.size[axis]){
+                continue;
+            }
+            if level == 0{
+                let mut arr = [T::default(); N];
+                for (axis, coordinate) in arr.iter_mut().enumerate(){
+                    *coordinate = self.lower_bound[axis] + T::from(offset[axis]).unwrap();
+                }
+                return Some(Point::new(arr));
+            }
+            let child_side = 1 << (level - 1);
+            for child_index in (0..(1usize << N)).rev(){
+                let mut child_offset = offset;
+                for (axis, coordinate) in child_offset.iter_mut().enumerate(){
+                    if child_index & (1 << axis) != 0{
+                        *coordinate += child_side;
+                    }
+                }
+                self.pending_cells.push((child_offset, level - 1));
+            }
+        }
+        None
+    }
+}
+
+
+
+#[cfg(test)]
+mod bounding_box_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn bounds_of_points() {
+        let points = vec![Point::new([1, -2]), Point::new([4, 0]), Point::new([2, 3])];
+        let bounding_box = BoundingBox::from_points(points.clone()).unwrap();
+        assert_eq!(bounding_box, BoundingBox::new(Point::new([1, -2]), Point::new([5, 4])));
+        assert!(points.iter().all(|point| bounding_box.contains(point)));
+        assert!(!bounding_box.contains(&Point::new([5, 0])));
+        assert_eq!(bounding_box.volume(), 24);
+        assert_eq!(BoundingBox::<i64, 2>::from_points(vec![]), None);
+    }
+
+    #[test]
+    fn set_operations() {
+        let first = BoundingBox::new(Point::new([0, 0, 0]), Point::new([3, 3, 3]));
+        let second = BoundingBox::new(Point::new([2, -1, 1]), Point::new([5, 2, 2]));
+        let far_away = BoundingBox::new(Point::new([10, 10, 10]), Point::new([11, 11, 11]));
+        assert_eq!(first.intersect(&second), Some(BoundingBox::new(Point::new([2, 0, 1]), Point::new([3, 2, 2]))));
+        assert_eq!(first.intersect(&far_away), None);
+        assert_eq!(first.union(&second), BoundingBox::new(Point::new([0, -1, 0]), Point::new([5, 3, 3])));
+        assert_eq!(first.expand(1), BoundingBox::new(Point::new([-1, -1, -1]), Point::new([4, 4, 4])));
+        assert_eq!(first.expand(1).volume(), 125);
+    }
+
+    #[test]
+    fn row_major_order() {
+        let bounding_box = BoundingBox::new(Point::new([1, 5]), Point::new([3, 7]));
+        let points: Vec<Point<i64, 2>> = bounding_box.points().collect();
+        assert_eq!(points, vec![Point::new([1, 5]), Point::new([2, 5]), Point::new([1, 6]), Point::new([2, 6])]);
+    }
+
+    #[test]
+    fn morton_order() {
+        let bounding_box = BoundingBox::new(Point::new([0, 0]), Point::new([3, 2]));
+        let points: Vec<Point<i64, 2>> = bounding_box.morton_points().collect();
+        let expected_points = vec![
+            Point::new([0, 0]), Point::new([1, 0]), Point::new([0, 1]), Point::new([1, 1]),
+            Point::new([2, 0]), Point::new([2, 1]),
+        ];
+        assert_eq!(points, expected_points);
+    }
+
+    #[rstest]
+    #[case([0, 0, 0], [1, 1, 1])]
+    #[case([-2, 3, 0], [5, 4, 6])]
+    #[case([0, 0, 0], [0, 4, 4])]
+    fn both_orders_visit_each_point_once(#[case] lower_bound: [i64; 3], #[case] upper_bound: [i64; 3]) {
+        let bounding_box = BoundingBox::new(Point::new(lower_bound), Point::new(upper_bound));
+        let mut row_major_points: Vec<Point<i64, 3>> = bounding_box.points().collect();
+        let mut morton_points: Vec<Point<i64, 3>> = bounding_box.morton_points().collect();
+        assert_eq!(row_major_points.len() as i64, bounding_box.volume());
+        assert!(row_major_points.iter().all(|point| bounding_box.contains(point)));
+        row_major_points.sort_by_key(|point| point.arr);
+        morton_points.sort_by_key(|point| point.arr);
+        assert_eq!(morton_points, row_major_points);
+    }
+}
@@ -0,0 +1,367 @@
+use std::{convert::TryFrom, error::Error, fmt::Display, ops::{Add, Mul, Neg, Sub}};
+
+use super::Point;
+use crate::error::ParseError;
+
+///
+/// The six directions to the neighbours of a hexagon in a grid with pointy tops,
+/// listed in counter-clockwise order starting east.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection{
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection{
+    pub fn all() -> [HexDirection; 6]{
+        [
+            HexDirection::East,
+            HexDirection::NorthEast,
+            HexDirection::NorthWest,
+            HexDirection::West,
+            HexDirection::SouthWest,
+            HexDirection::SouthEast,
+        ]
+    }
+
+    fn index(self) -> usize{
+        self as usize
+    }
+
+    ///
+    /// The offset to the neighbour in this direction. North is towards negative `r`.
+    ///
+    pub fn offset(self) -> Axial{
+        match self{
+            HexDirection::East => Axial::new(1, 0),
+            HexDirection::NorthEast => Axial::new(1, -1),
+            HexDirection::NorthWest => Axial::new(0, -1),
+            HexDirection::West => Axial::new(-1, 0),
+            HexDirection::SouthWest => Axial::new(-1, 1),
+            HexDirection::SouthEast => Axial::new(0, 1),
+        }
+    }
+
+    ///
+    /// Rotates the direction by `steps` times 60 degrees in counter-clockwise direction.
+    ///
+    pub fn rotate(self, steps: i64) -> HexDirection{
+        let index = (self.index() as i64 + steps).rem_euclid(6) as usize;
+        HexDirection::all()[index]
+    }
+
+    pub fn as_str(self) -> &'static str{
+        match self{
+            HexDirection::East => "e",
+            HexDirection::NorthEast => "ne",
+            HexDirection::NorthWest => "nw",
+            HexDirection::West => "w",
+            HexDirection::SouthWest => "sw",
+            HexDirection::SouthEast => "se",
+        }
+    }
+}
+
+impl TryFrom<&str> for HexDirection{
+    type Error = ParseHexDirectionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        HexDirection::all().iter()
+            .copied()
+            .find(|direction| direction.as_str() == value)
+            .ok_or_else(|| ParseHexDirectionError { invalid_input: String::from(value) })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseHexDirectionError{
+    pub invalid_input: String,
+}
+
+impl Display for ParseHexDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let valid_values: Vec<&str> = HexDirection::all().iter()
+            .map(|direction| direction.as_str())
+            .collect();
+        write!(f, "Invalid input {} to parse as a hexagonal direction. The valid values are {}.", self.invalid_input, valid_values.join(", "))
+    }
+}
+
+impl Error for ParseHexDirectionError {}
+
+///
+/// Axial coordinates of a hexagon. The third cube coordinate is implicitly `s = -q - r`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Axial{
+    pub q: i64,
+    pub r: i64,
+}
+
+///
+/// Cube coordinates of a hexagon, which always satisfy `q + r + s = 0`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Cube{
+    q: i64,
+    r: i64,
+    s: i64,
+}
+
+impl Axial{
+    pub fn new(q: i64, r: i64) -> Axial{
+        Axial {q, r}
+    }
+
+    pub fn origin() -> Axial{
+        Axial::default()
+    }
+
+    pub fn neighbour(self, direction: HexDirection) -> Axial{
+        self + direction.offset()
+    }
+
+    pub fn neighbours(self) -> [Axial; 6]{
+        let mut neighbours = [self; 6];
+        for (neighbour, direction) in neighbours.iter_mut().zip(HexDirection::all().iter()){
+            *neighbour = self.neighbour(*direction);
+        }
+        neighbours
+    }
+
+    ///
+    /// The number of steps between neighbours needed to get from one hexagon to the other.
+    ///
+    pub fn distance(self, other: Axial) -> i64{
+        Cube::from(self).distance(Cube::from(other))
+    }
+
+    ///
+    /// Rotates the coordinates around the origin by `steps` times 60 degrees in counter-clockwise direction.
+    ///
+    pub fn rotate(self, steps: i64) -> Axial{
+        Axial::from(Cube::from(self).rotate(steps))
+    }
+
+    pub fn rotate_around(self, center: Axial, steps: i64) -> Axial{
+        center + (self - center).rotate(steps)
+    }
+
+    ///
+    /// The hexagons at exactly `radius` steps from `self` in counter-clockwise order.
+    ///
+    pub fn ring(self, radius: usize) -> Vec<Axial>{
+        if radius == 0{
+            return vec![self];
+        }
+        let mut ring = Vec::with_capacity(6 * radius);
+        let mut current = self + HexDirection::SouthWest.offset() * radius as i64;
+        for direction in HexDirection::all().iter(){
+            for _ in 0..radius{
+                ring.push(current);
+                current = current.neighbour(*direction);
+            }
+        }
+        ring
+    }
+
+    ///
+    /// The hexagons at most `radius` steps from `self`, starting with `self` and continuing ring by ring.
+    ///
+    pub fn spiral(self, radius: usize) -> Vec<Axial>{
+        (0..=radius)
+            .flat_map(|ring_radius| self.ring(ring_radius))
+            .collect()
+    }
+}
+
+impl Cube{
+    ///
+    /// Returns `None` unless the coordinates add up to zero.
+    ///
+    pub fn new(q: i64, r: i64, s: i64) -> Option<Cube>{
+        if q + r + s == 0 { Some(Cube {q, r, s}) } else { None }
+    }
+
+    pub fn q(&self) -> i64{
+        self.q
+    }
+
+    pub fn r(&self) -> i64{
+        self.r
+    }
+
+    pub fn s(&self) -> i64{
+        self.s
+    }
+
+    pub fn distance(self, other: Cube) -> i64{
+        let differences = [self.q - other.q, self.r - other.r, self.s - other.s];
+        differences.iter()
+            .map(|difference| difference.abs())
+            .max()
+            .unwrap()
+    }
+
+    ///
+    /// Rotates the coordinates around the origin by `steps` times 60 degrees in counter-clockwise direction.
+    ///
+    pub fn rotate(self, steps: i64) -> Cube{
+        let mut rotated = self;
+        for _ in 0..steps.rem_euclid(6){
+            rotated = Cube {q: -rotated.s, r: -rotated.q, s: -rotated.r};
+        }
+        rotated
+    }
+}
+
+impl From<Axial> for Cube{
+    fn from(axial: Axial) -> Self {
+        Cube {q: axial.q, r: axial.r, s: -axial.q - axial.r}
+    }
+}
+
+impl From<Cube> for Axial{
+    fn from(cube: Cube) -> Self {
+        Axial {q: cube.q, r: cube.r}
+    }
+}
+
+impl From<Axial> for Point<i64, 2>{
+    fn from(axial: Axial) -> Self {
+        Point::new([axial.q, axial.r])
+    }
+}
+
+impl From<Point<i64, 2>> for Axial{
+    fn from(point: Point<i64, 2>) -> Self {
+        Axial {q: point[0], r: point[1]}
+    }
+}
+
+impl Add for Axial {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output{
+        Axial {q: self.q + other.q, r: self.r + other.r}
+    }
+}
+
+impl Sub for Axial {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output{
+        Axial {q: self.q - other.q, r: self.r - other.r}
+    }
+}
+
+impl Neg for Axial {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output{
+        Axial {q: -self.q, r: -self.r}
+    }
+}
+
+impl Mul<i64> for Axial {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output{
+        Axial {q: self.q * rhs, r: self.r * rhs}
+    }
+}
+
+///
+/// Sequence of steps between neighbouring hexagons.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexPath(pub Vec<HexDirection>);
+
+impl HexPath{
+    ///
+    /// Parses directions written without separators, e.g. `nwwswee`.
+    ///
+    pub fn parse(text: &str) -> Result<HexPath, ParseError>{
+        let mut path = vec![];
+        let mut column_index = 0;
+        while column_index < text.len(){
+            let direction_length = if text[column_index..].starts_with(&['n', 's'][..]) { 2 } else { 1 };
+            let direction_text = text.get(column_index..(column_index + direction_length))
+                .ok_or_else(|| ParseError::in_line(column_index, "Incomplete hexagonal direction."))?;
+            let direction = HexDirection::try_from(direction_text)
+                .map_err(|error| ParseError::in_line(column_index, &error.to_string()))?;
+            path.push(direction);
+            column_index += direction_length;
+        }
+        Ok(HexPath(path))
+    }
+
+    pub fn end(&self, start: Axial) -> Axial{
+        self.0.iter()
+            .fold(start, |current, direction| current.neighbour(*direction))
+    }
+}
+
+
+
+#[cfg(test)]
+mod hex_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("nwwswee", Axial::new(0, 0))]
+    #[case("esew", Axial::new(0, 1))]
+    #[case("nenene", Axial::new(3, -3))]
+    fn path_ends(#[case] text: &str, #[case] expected_end: Axial) {
+        assert_eq!(HexPath::parse(text).unwrap().end(Axial::origin()), expected_end);
+    }
+
+    #[rstest]
+    #[case("nwx", 3)]
+    #[case("en", 2)]
+    fn invalid_paths_are_reported_with_their_column(#[case] text: &str, #[case] expected_column: usize) {
+        assert_eq!(HexPath::parse(text).unwrap_err().column, expected_column);
+    }
+
+    #[test]
+    fn rotations() {
+        assert_eq!(HexDirection::East.offset().rotate(1), HexDirection::NorthEast.offset());
+        assert_eq!(HexDirection::SouthEast.rotate(2), HexDirection::NorthEast);
+        for direction in HexDirection::all().iter(){
+            assert_eq!(direction.offset().rotate(1), direction.rotate(1).offset());
+            assert_eq!(direction.offset().rotate(-1), direction.rotate(-1).offset());
+        }
+        let center = Axial::new(2, -1);
+        let point = Axial::new(4, 3);
+        assert_eq!(point.rotate_around(center, 6), point);
+        assert_eq!(point.rotate_around(center, 1).distance(center), point.distance(center));
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(4)]
+    fn rings_and_spirals(#[case] radius: usize) {
+        let center = Axial::new(-3, 5);
+        let ring = center.ring(radius);
+        assert_eq!(ring.len(), if radius == 0 { 1 } else { 6 * radius });
+        assert!(ring.iter().all(|hex| hex.distance(center) == radius as i64));
+        assert_eq!(center.spiral(radius).len(), 1 + 3 * radius * (radius + 1));
+    }
+
+    #[test]
+    fn cube_coordinates() {
+        let axial = Axial::new(3, -5);
+        let cube = Cube::from(axial);
+        assert_eq!(cube.s(), 2);
+        assert_eq!(Axial::from(cube), axial);
+        assert_eq!(Cube::new(1, 1, 1), None);
+        assert_eq!(axial.distance(Axial::origin()), 5);
+    }
+}
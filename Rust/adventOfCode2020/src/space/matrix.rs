@@ -0,0 +1,190 @@
+use std::ops::{Index, IndexMut, Mul};
+
+use super::Vector;
+use crate::util;
+
+///
+/// Matrix with `R` rows and `C` columns, stored row by row.
+///
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Matrix<T, const R: usize, const C: usize>{
+    rows: [[T; C]; R],
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>{
+    pub fn new(rows: [[T; C]; R]) -> Matrix<T, R, C>{
+        Matrix {rows}
+    }
+}
+
+impl<T: num::Zero + Copy, const R: usize, const C: usize> Matrix<T, R, C>{
+    pub fn zero() -> Matrix<T, R, C>{
+        Matrix {rows: [[T::zero(); C]; R]}
+    }
+
+    pub fn transpose(&self) -> Matrix<T, C, R>{
+        let mut rows = [[T::zero(); R]; C];
+        for (row_index, row) in self.rows.iter().enumerate(){
+            for (column_index, value) in row.iter().enumerate(){
+                rows[column_index][row_index] = *value;
+            }
+        }
+        Matrix {rows}
+    }
+
+    pub fn row(&self, index: usize) -> Vector<T, C>{
+        Vector::new(self.rows[index])
+    }
+
+    pub fn column(&self, index: usize) -> Vector<T, R>{
+        let mut arr = [T::zero(); R];
+        for (row_index, row) in self.rows.iter().enumerate(){
+            arr[row_index] = row[index];
+        }
+        Vector::new(arr)
+    }
+}
+
+impl<T: num::Zero + num::One + Copy, const N: usize> Matrix<T, N, N>{
+    pub fn identity() -> Matrix<T, N, N>{
+        let mut matrix = Matrix::zero();
+        for index in 0..N{
+            matrix.rows[index][index] = T::one();
+        }
+        matrix
+    }
+}
+
+impl<T: num::Num + Copy, const N: usize> Matrix<T, N, N>{
+    ///
+    /// Computes the determinant using the fraction-free Bareiss algorithm.
+    /// All divisions are exact, so the result is exact for integer matrices, too.
+    ///
+    pub fn determinant(&self) -> T{
+        if N == 0{
+            return T::one();
+        }
+        let mut rows = self.rows;
+        let mut sign = T::one();
+        let mut previous_pivot = T::one();
+        for pivot_index in 0..(N - 1){
+            if rows[pivot_index][pivot_index].is_zero(){
+                let swap_index = match (pivot_index + 1..N).find(|row_index| !rows[*row_index][pivot_index].is_zero()){
+                    Some(row_index) => row_index,
+                    None => return T::zero(),
+                };
+                rows.swap(pivot_index, swap_index);
+                sign = T::zero() - sign;
+            }
+            let pivot = rows[pivot_index][pivot_index];
+            for row_index in (pivot_index + 1)..N{
+                for column_index in (pivot_index + 1)..N{
+                    let value = rows[row_index][column_index] * pivot - rows[row_index][pivot_index] * rows[pivot_index][column_index];
+                    rows[row_index][column_index] = value / previous_pivot;
+                }
+            }
+            previous_pivot = pivot;
+        }
+        sign * rows[N - 1][N - 1]
+    }
+}
+
+impl<T: num::Signed + Copy> Matrix<T, 2, 2>{
+    ///
+    /// Rotation by a multiple of 90 degrees in counter-clockwise direction.
+    ///
+    pub fn quarter_turn_rotation(angle: i64) -> Matrix<T, 2, 2>{
+        let (zero, one) = (T::zero(), T::one());
+        let rotation_steps: i64 = util::modulo(angle, 360) / 90;
+        match rotation_steps{
+            0 => Matrix::new([[one, zero], [zero, one]]),
+            1 => Matrix::new([[zero, -one], [one, zero]]),
+            2 => Matrix::new([[-one, zero], [zero, -one]]),
+            3 => Matrix::new([[zero, one], [-one, zero]]),
+            _ => unreachable!("This cannot happen because of how modulo works."),
+        }
+    }
+
+    ///
+    /// Reflection along the y-axis, i.e. negating the x-coordinate.
+    ///
+    pub fn reflection_on_y_axis() -> Matrix<T, 2, 2>{
+        Matrix::new([[-T::one(), T::zero()], [T::zero(), T::one()]])
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for Matrix<T, R, C>{
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output{
+        &self.rows[row][column]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<T, R, C>{
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output{
+        &mut self.rows[row][column]
+    }
+}
+
+impl<T: num::Num + Copy, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>> for Matrix<T, R, C>{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, other: Matrix<T, C, K>) -> Self::Output{
+        let mut rows = [[T::zero(); K]; R];
+        for (row_index, row) in rows.iter_mut().enumerate(){
+            for (column_index, value) in row.iter_mut().enumerate(){
+                *value = self.row(row_index).dot(other.column(column_index));
+            }
+        }
+        Matrix {rows}
+    }
+}
+
+impl<T: num::Num + Copy, const R: usize, const C: usize> Mul<Vector<T, C>> for Matrix<T, R, C>{
+    type Output = Vector<T, R>;
+
+    fn mul(self, vector: Vector<T, C>) -> Self::Output{
+        let mut arr = [T::zero(); R];
+        for (row_index, value) in arr.iter_mut().enumerate(){
+            *value = self.row(row_index).dot(vector);
+        }
+        Vector::new(arr)
+    }
+}
+
+
+
+#[cfg(test)]
+mod matrix_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn multiplication() {
+        let left = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let right = Matrix::new([[7, 8], [9, 10], [11, 12]]);
+        assert_eq!(left * right, Matrix::new([[58, 64], [139, 154]]));
+        assert_eq!(left * Vector::new([1, 0, -1]), Vector::new([-2, -2]));
+        assert_eq!(left.transpose(), Matrix::new([[1, 4], [2, 5], [3, 6]]));
+    }
+
+    #[rstest]
+    #[case(Matrix::new([[2, 0, 0], [0, 3, 0], [0, 0, 4]]), 24)]
+    #[case(Matrix::new([[0, 1, 2], [1, 0, 3], [4, -3, 8]]), -2)]
+    #[case(Matrix::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]), 0)]
+    #[case(Matrix::new([[0, 0, 1], [0, 1, 0], [1, 0, 0]]), -1)]
+    fn determinant(#[case] matrix: Matrix<i64, 3, 3>, #[case] expected_determinant: i64) {
+        assert_eq!(matrix.determinant(), expected_determinant);
+    }
+
+    #[rstest]
+    #[case(90, Vector::new([-2, 1]))]
+    #[case(-90, Vector::new([2, -1]))]
+    #[case(540, Vector::new([-1, -2]))]
+    fn quarter_turn_rotation(#[case] angle: i64, #[case] expected_vector: Vector<i64, 2>) {
+        let rotation: Matrix<i64, 2, 2> = Matrix::quarter_turn_rotation(angle);
+        assert_eq!(rotation * Vector::new([1, 2]), expected_vector);
+        assert_eq!(rotation.determinant(), 1);
+    }
+}
@@ -0,0 +1,223 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::{BoundingBox, Point, Vector};
+use super::hex::HexDirection;
+
+///
+/// How far a neighbourhood reaches along each of its offsets.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reach{
+    ///
+    /// The neighbour is the point moved by the offset.
+    ///
+    Adjacent,
+    ///
+    /// The neighbour is the first matching point along the ray in the direction of the offset.
+    ///
+    LineOfSight,
+}
+
+///
+/// Set of offsets to the neighbours of a point. The offsets are shared between clones,
+/// and those of the Moore and von Neumann neighbourhoods are computed only once per coordinate type and dimension.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Neighbourhood<T, const N: usize>{
+    offsets: Arc<Vec<Vector<T, N>>>,
+    reach: Reach,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum UnitNeighbourhood{
+    Moore,
+    VonNeumann,
+}
+
+//The offsets by the type of the coordinates, the kind of neighbourhood and the dimension.
+type OffsetCache = HashMap<(TypeId, UnitNeighbourhood, usize), Arc<dyn Any + Send + Sync>>;
+
+lazy_static! {
+    static ref UNIT_OFFSETS: Mutex<OffsetCache> = Mutex::new(HashMap::new());
+}
+
+impl<T: num::PrimInt + num::Signed + Default, const N: usize> Neighbourhood<T, N>{
+    ///
+    /// The `3^N - 1` points differing by at most one in each coordinate.
+    ///
+    pub fn moore() -> Neighbourhood<T, N> where T: Send + Sync + 'static{
+        Neighbourhood::cached(UnitNeighbourhood::Moore, || Neighbourhood::moore_with_radius(T::one()))
+    }
+
+    ///
+    /// The `2 * N` points differing by one in exactly one coordinate.
+    ///
+    pub fn von_neumann() -> Neighbourhood<T, N> where T: Send + Sync + 'static{
+        Neighbourhood::cached(UnitNeighbourhood::VonNeumann, || Neighbourhood::von_neumann_with_radius(T::one()))
+    }
+
+    fn cached(kind: UnitNeighbourhood, compute: impl FnOnce() -> Neighbourhood<T, N>) -> Neighbourhood<T, N> where T: Send + Sync + 'static{
+        let mut unit_offsets = UNIT_OFFSETS.lock().unwrap();
+        let offsets = unit_offsets.entry((TypeId::of::<T>(), kind, N))
+            .or_insert_with(|| compute().offsets)
+            .clone();
+        Neighbourhood {offsets: offsets.downcast().unwrap(), reach: Reach::Adjacent}
+    }
+
+    ///
+    /// The points within the given distance with respect to the maximum norm.
+    ///
+    pub fn moore_with_radius(radius: T) -> Neighbourhood<T, N>{
+        Neighbourhood::within_radius(radius, |offset| offset.linf_norm())
+    }
+
+    ///
+    /// The points within the given manhattan distance.
+    ///
+    pub fn von_neumann_with_radius(radius: T) -> Neighbourhood<T, N>{
+        Neighbourhood::within_radius(radius, |offset| offset.l1_norm())
+    }
+
+    fn within_radius(radius: T, norm: impl Fn(Vector<T, N>) -> T) -> Neighbourhood<T, N>{
+        let origin = Point::new([T::zero(); N]);
+        let offsets = BoundingBox::from_points(std::iter::once(origin))
+            .unwrap()
+            .expand(radius)
+            .points()
+            .map(|point| point - origin)
+            .filter(|offset| norm(*offset) <= radius);
+        Neighbourhood::custom(offsets)
+    }
+
+    ///
+    /// A neighbourhood with arbitrary offsets. The zero offset and repeated offsets are ignored.
+    ///
+    pub fn custom(offsets: impl IntoIterator<Item = Vector<T, N>>) -> Neighbourhood<T, N>{
+        let zero = Vector::new([T::zero(); N]);
+        let mut unique_offsets: Vec<Vector<T, N>> = vec![];
+        for offset in offsets{
+            if offset != zero && !unique_offsets.contains(&offset){
+                unique_offsets.push(offset);
+            }
+        }
+        Neighbourhood {offsets: Arc::new(unique_offsets), reach: Reach::Adjacent}
+    }
+
+    ///
+    /// The same neighbourhood, but looking along the offsets until a matching point is found.
+    ///
+    pub fn line_of_sight(self) -> Neighbourhood<T, N>{
+        Neighbourhood {reach: Reach::LineOfSight, ..self}
+    }
+
+    pub fn offsets(&self) -> &[Vector<T, N>]{
+        &self.offsets
+    }
+
+    pub fn reach(&self) -> Reach{
+        self.reach
+    }
+
+    ///
+    /// The points moved by each of the offsets, regardless of the reach.
+    ///
+    pub fn neighbours<'a>(&'a self, point: &Point<T, N>) -> impl Iterator<Item = Point<T, N>> + 'a{
+        let point = *point;
+        self.offsets.iter()
+            .map(move |offset| point + *offset)
+    }
+
+    ///
+    /// The neighbours satisfying `is_match`. With line of sight reach, each ray continues past points that do not match
+    /// until it finds a matching point or leaves the region described by `is_inside`.
+    ///
+    pub fn neighbours_matching(&self, point: &Point<T, N>, is_inside: impl Fn(&Point<T, N>) -> bool, is_match: impl Fn(&Point<T, N>) -> bool) -> Vec<Point<T, N>>{
+        match self.reach{
+            Reach::Adjacent => self.neighbours(point)
+                .filter(|neighbour| is_inside(neighbour) && is_match(neighbour))
+                .collect(),
+            Reach::LineOfSight => self.offsets.iter()
+                .filter_map(|offset| {
+                    let mut potential_neighbour = *point + *offset;
+                    while is_inside(&potential_neighbour){
+                        if is_match(&potential_neighbour){
+                            return Some(potential_neighbour);
+                        }
+                        potential_neighbour = potential_neighbour + *offset;
+                    }
+                    None
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Neighbourhood<i64, 2>{
+    ///
+    /// The six neighbours of a hexagon in axial coordinates, see `Axial`.
+    ///
+    pub fn hex() -> Neighbourhood<i64, 2>{
+        let offsets: Vec<Vector<i64, 2>> = HexDirection::all().iter()
+            .map(|direction| Point::from(direction.offset()) - Point::new([0, 0]))
+            .collect();
+        Neighbourhood::custom(offsets)
+    }
+}
+
+
+
+#[cfg(test)]
+mod neighbourhood_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Neighbourhood::<i64, 3>::moore(), 26)]
+    #[case(Neighbourhood::<i64, 3>::von_neumann(), 6)]
+    #[case(Neighbourhood::<i64, 3>::moore_with_radius(2), 124)]
+    #[case(Neighbourhood::<i64, 3>::von_neumann_with_radius(2), 24)]
+    #[case(Neighbourhood::<i64, 3>::custom(vec![Vector::new([1, 0, 0]), Vector::new([0, 0, 0]), Vector::new([1, 0, 0])]), 1)]
+    fn neighbourhood_sizes(#[case] neighbourhood: Neighbourhood<i64, 3>, #[case] expected_size: usize) {
+        assert_eq!(neighbourhood.offsets().len(), expected_size);
+    }
+
+    #[test]
+    fn moore_neighbourhood_in_four_dimensions() {
+        let neighbourhood = Neighbourhood::<i64, 4>::moore();
+        assert_eq!(neighbourhood.offsets().len(), 80);
+        assert!(neighbourhood.offsets().iter().all(|offset| offset.linf_norm() == 1));
+    }
+
+    #[test]
+    fn unit_neighbourhoods_are_computed_once_per_dimension() {
+        assert_eq!(Neighbourhood::<i64, 3>::moore().offsets().as_ptr(), Neighbourhood::<i64, 3>::moore().offsets().as_ptr());
+        assert_ne!(Neighbourhood::<i64, 3>::moore().offsets().as_ptr(), Neighbourhood::<i64, 3>::von_neumann().offsets().as_ptr());
+        assert_eq!(Neighbourhood::<i64, 2>::moore().offsets().len(), 8);
+        assert_eq!(Neighbourhood::<i128, 3>::moore().offsets().len(), 26);
+    }
+
+    #[test]
+    fn hex_neighbourhood_matches_the_axial_neighbours() {
+        let center = crate::space::Axial::new(2, -3);
+        let neighbours: Vec<Point<i64, 2>> = Neighbourhood::hex().neighbours(&Point::from(center)).collect();
+        let expected_neighbours: Vec<Point<i64, 2>> = center.neighbours().iter()
+            .map(|neighbour| Point::from(*neighbour))
+            .collect();
+        assert_eq!(neighbours, expected_neighbours);
+    }
+
+    #[test]
+    fn line_of_sight() {
+        let is_inside = |point: &Point<i64, 2>| (0..5).contains(&point[0]) && (0..5).contains(&point[1]);
+        let is_match = |point: &Point<i64, 2>| point[0] == 4 || point[1] == 0;
+        let point = Point::new([1, 2]);
+        let mut adjacent_neighbours = Neighbourhood::von_neumann().neighbours_matching(&point, is_inside, is_match);
+        let mut visible_neighbours = Neighbourhood::von_neumann().line_of_sight().neighbours_matching(&point, is_inside, is_match);
+        adjacent_neighbours.sort_by_key(|point| point.arr);
+        visible_neighbours.sort_by_key(|point| point.arr);
+        assert_eq!(adjacent_neighbours, vec![]);
+        assert_eq!(visible_neighbours, vec![Point::new([1, 0]), Point::new([4, 2])]);
+    }
+}
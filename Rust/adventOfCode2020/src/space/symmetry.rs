@@ -0,0 +1,320 @@
+use std::ops::Neg;
+
+use super::{AffineTransform, Matrix, Point, Vector};
+
+///
+/// Symmetry of the N-dimensional grid, i.e. a combination of rotations by multiples of 90 degrees and reflections.
+/// Each coordinate of the image is a coordinate of the original, possibly negated:
+/// coordinate `i` of the image is coordinate `permutation[i]` of the original, negated if `negations[i]` is set.
+/// Together, these form the signed permutation group with `2^N * N!` elements.
+///
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct SignedPermutation<const N: usize>{
+    permutation: [usize; N],
+    negations: [bool; N],
+}
+
+///
+/// The symmetries of a square, i.e. the dihedral group with 8 elements.
+///
+pub type D4 = SignedPermutation<2>;
+
+impl<const N: usize> SignedPermutation<N>{
+    ///
+    /// Returns `None` unless `permutation` contains each of the indices `0..N` exactly once.
+    ///
+    pub fn new(permutation: [usize; N], negations: [bool; N]) -> Option<SignedPermutation<N>>{
+        let mut is_used = [false; N];
+        for index in permutation.iter(){
+            if *index >= N || is_used[*index]{
+                return None;
+            }
+            is_used[*index] = true;
+        }
+        Some(SignedPermutation {permutation, negations})
+    }
+
+    pub fn identity() -> SignedPermutation<N>{
+        let mut permutation = [0; N];
+        for (index, target) in permutation.iter_mut().enumerate(){
+            *target = index;
+        }
+        SignedPermutation {permutation, negations: [false; N]}
+    }
+
+    ///
+    /// Swaps the two axes.
+    ///
+    pub fn transposition(first_axis: usize, second_axis: usize) -> SignedPermutation<N>{
+        let mut transposition = SignedPermutation::identity();
+        transposition.permutation.swap(first_axis, second_axis);
+        transposition
+    }
+
+    ///
+    /// Negates the coordinate along the axis.
+    ///
+    pub fn reflection(axis: usize) -> SignedPermutation<N>{
+        let mut reflection = SignedPermutation::identity();
+        reflection.negations[axis] = true;
+        reflection
+    }
+
+    ///
+    /// Rotation by `turns` times 90 degrees in the plane of the two axes, turning the first axis towards the second one.
+    ///
+    pub fn quarter_turn_in_plane(first_axis: usize, second_axis: usize, turns: i64) -> SignedPermutation<N>{
+        let mut single_turn = SignedPermutation::transposition(first_axis, second_axis);
+        single_turn.negations[first_axis] = true;
+        let mut rotation = SignedPermutation::identity();
+        for _ in 0..turns.rem_euclid(4){
+            rotation = rotation.then(&single_turn);
+        }
+        rotation
+    }
+
+    ///
+    /// All elements of the group, starting with the identity.
+    ///
+    pub fn all() -> Vec<SignedPermutation<N>>{
+        let mut elements = vec![];
+        for permutation in permutations::<N>(){
+            for negation_mask in 0..(1usize << N){
+                let mut negations = [false; N];
+                for (index, negation) in negations.iter_mut().enumerate(){
+                    *negation = negation_mask & (1 << index) != 0;
+                }
+                elements.push(SignedPermutation {permutation, negations});
+            }
+        }
+        elements
+    }
+
+    ///
+    /// The symmetry applying `self` first and `other` afterwards.
+    ///
+    pub fn then(&self, other: &SignedPermutation<N>) -> SignedPermutation<N>{
+        let mut permutation = [0; N];
+        let mut negations = [false; N];
+        for index in 0..N{
+            let intermediate_index = other.permutation[index];
+            permutation[index] = self.permutation[intermediate_index];
+            negations[index] = other.negations[index] != self.negations[intermediate_index];
+        }
+        SignedPermutation {permutation, negations}
+    }
+
+    pub fn inverse(&self) -> SignedPermutation<N>{
+        let mut permutation = [0; N];
+        let mut negations = [false; N];
+        for (index, source_index) in self.permutation.iter().enumerate(){
+            permutation[*source_index] = index;
+            negations[*source_index] = self.negations[index];
+        }
+        SignedPermutation {permutation, negations}
+    }
+
+    ///
+    /// The axis of the original that coordinate `axis` of the image comes from.
+    ///
+    pub fn source_axis(&self, axis: usize) -> usize{
+        self.permutation[axis]
+    }
+
+    ///
+    /// Whether coordinate `axis` of the image is negated.
+    ///
+    pub fn is_negated(&self, axis: usize) -> bool{
+        self.negations[axis]
+    }
+
+    ///
+    /// Whether the symmetry preserves orientation, i.e. is a pure rotation without reflection.
+    ///
+    pub fn is_rotation(&self) -> bool{
+        let negation_count = self.negations.iter().filter(|negation| **negation).count();
+        let mut inversion_count = 0;
+        for first in 0..N{
+            for second in (first + 1)..N{
+                if self.permutation[first] > self.permutation[second]{
+                    inversion_count += 1;
+                }
+            }
+        }
+        (negation_count + inversion_count) % 2 == 0
+    }
+
+    pub fn apply<T: Neg<Output = T> + Default + Copy>(&self, vector: Vector<T, N>) -> Vector<T, N>{
+        let mut arr = [T::default(); N];
+        for (index, coordinate) in arr.iter_mut().enumerate(){
+            let value = vector[self.permutation[index]];
+            *coordinate = if self.negations[index] { -value } else { value };
+        }
+        Vector::new(arr)
+    }
+
+    pub fn matrix<T: num::Signed + Copy>(&self) -> Matrix<T, N, N>{
+        let mut matrix = Matrix::zero();
+        for index in 0..N{
+            matrix[(index, self.permutation[index])] = if self.negations[index] { -T::one() } else { T::one() };
+        }
+        matrix
+    }
+
+    ///
+    /// The size of the box `[0, size[0]) x ... x [0, size[N-1])` after applying the symmetry.
+    ///
+    pub fn transformed_size(&self, size: [usize; N]) -> [usize; N]{
+        let mut transformed_size = [0; N];
+        for (index, extent) in transformed_size.iter_mut().enumerate(){
+            *extent = size[self.permutation[index]];
+        }
+        transformed_size
+    }
+
+    ///
+    /// Applies the symmetry to a point in the box `[0, size[0]) x ... x [0, size[N-1])`
+    /// and moves the result into the transformed box starting at the origin.
+    ///
+    pub fn apply_in_box(&self, point: Point<usize, N>, size: [usize; N]) -> Point<usize, N>{
+        let mut arr = [0; N];
+        for (index, coordinate) in arr.iter_mut().enumerate(){
+            let source_index = self.permutation[index];
+            *coordinate = if self.negations[index] { size[source_index] - 1 - point[source_index] } else { point[source_index] };
+        }
+        Point::new(arr)
+    }
+
+    ///
+    /// The affine transform corresponding to `apply_in_box`.
+    ///
+    pub fn transform_in_box(&self, size: [usize; N]) -> AffineTransform<i64, N>{
+        let mut translation = [0; N];
+        for (index, offset) in translation.iter_mut().enumerate(){
+            if self.negations[index]{
+                *offset = size[self.permutation[index]] as i64 - 1;
+            }
+        }
+        AffineTransform::new(self.matrix(), Vector::new(translation))
+    }
+}
+
+impl SignedPermutation<2>{
+    ///
+    /// Rotation by `turns` times 90 degrees in counter-clockwise direction.
+    ///
+    pub fn quarter_turn(turns: i64) -> D4{
+        D4::quarter_turn_in_plane(0, 1, turns)
+    }
+
+    ///
+    /// Reflection along the y-axis, i.e. negating the x-coordinate.
+    ///
+    pub fn reflection_on_y_axis() -> D4{
+        D4::reflection(0)
+    }
+
+    ///
+    /// The symmetry that first reflects along the y-axis, if requested, and then rotates by `turns` quarter turns.
+    ///
+    pub fn from_reflection_and_rotation(is_reflection: bool, turns: u8) -> D4{
+        let reflection = if is_reflection { D4::reflection_on_y_axis() } else { D4::identity() };
+        reflection.then(&D4::quarter_turn(i64::from(turns)))
+    }
+
+    pub fn is_reflection(&self) -> bool{
+        !self.is_rotation()
+    }
+
+    ///
+    /// The number of counter-clockwise quarter turns in the decomposition of `from_reflection_and_rotation`.
+    ///
+    pub fn quarter_turns(&self) -> u8{
+        let rotation = if self.is_reflection() { D4::reflection_on_y_axis().then(self) } else { *self };
+        (0..4).find(|turns| D4::quarter_turn(i64::from(*turns)) == rotation)
+            .expect("Every rotation in D4 is a multiple of a quarter turn.")
+    }
+}
+
+///
+/// All permutations of `0..N` in lexicographic order.
+///
+fn permutations<const N: usize>() -> Vec<[usize; N]>{
+    let mut permutations = vec![];
+    let mut current = SignedPermutation::<N>::identity().permutation;
+    loop{
+        permutations.push(current);
+        let pivot = match (1..N).rev().find(|index| current[index - 1] < current[*index]){
+            Some(index) => index - 1,
+            None => return permutations,
+        };
+        let successor = (pivot + 1..N).rev()
+            .find(|index| current[*index] > current[pivot])
+            .unwrap();
+        current.swap(pivot, successor);
+        current[pivot + 1..].reverse();
+    }
+}
+
+
+
+#[cfg(test)]
+mod symmetry_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashSet;
+
+    #[test]
+    fn group_sizes() {
+        assert_eq!(D4::all().len(), 8);
+        assert_eq!(SignedPermutation::<3>::all().len(), 48);
+        assert_eq!(SignedPermutation::<4>::all().into_iter().collect::<HashSet<_>>().len(), 384);
+        assert_eq!(SignedPermutation::<3>::all().iter().filter(|element| element.is_rotation()).count(), 24);
+    }
+
+    #[test]
+    fn group_operations_match_the_matrices() {
+        let elements = SignedPermutation::<3>::all();
+        let vector = Vector::new([1, 2, 3]);
+        for first in &elements{
+            assert_eq!(first.then(&first.inverse()), SignedPermutation::identity());
+            assert_eq!(first.matrix::<i64>() * vector, first.apply(vector));
+            assert_eq!(first.matrix::<i64>().determinant() == 1, first.is_rotation());
+            for second in &elements{
+                assert_eq!(first.then(second).apply(vector), second.apply(first.apply(vector)));
+            }
+        }
+    }
+
+    #[test]
+    fn rotations_in_planes() {
+        let rotation = SignedPermutation::<3>::quarter_turn_in_plane(0, 2, 1);
+        assert_eq!(rotation.apply(Vector::new([1, 2, 3])), Vector::new([-3, 2, 1]));
+        assert_eq!(SignedPermutation::<3>::quarter_turn_in_plane(0, 2, 4), SignedPermutation::identity());
+        assert_eq!(SignedPermutation::<3>::transposition(1, 2).apply(Vector::new([1, 2, 3])), Vector::new([1, 3, 2]));
+        assert_eq!(SignedPermutation::<3>::reflection(1).apply(Vector::new([1, 2, 3])), Vector::new([1, -2, 3]));
+    }
+
+    #[rstest]
+    #[case(false, 0, Point::new([2, 0]))]
+    #[case(false, 1, Point::new([1, 2]))]
+    #[case(false, 2, Point::new([0, 1]))]
+    #[case(true, 0, Point::new([0, 0]))]
+    #[case(true, 1, Point::new([1, 0]))]
+    fn application_in_a_box(#[case] is_reflection: bool, #[case] turns: u8, #[case] expected_point: Point<usize, 2>) {
+        let size = [3, 2];
+        let symmetry = D4::from_reflection_and_rotation(is_reflection, turns);
+        let point = Point::new([2, 0]);
+        let transformed_point = symmetry.apply_in_box(point, size);
+        assert_eq!(transformed_point, expected_point);
+        assert_eq!(symmetry.transform_in_box(size).apply(Point::new([2, 0])), Point::new([expected_point[0] as i64, expected_point[1] as i64]));
+        assert_eq!(symmetry.inverse().apply_in_box(transformed_point, symmetry.transformed_size(size)), point);
+    }
+
+    #[test]
+    fn decomposition_into_reflection_and_rotation() {
+        for symmetry in D4::all(){
+            assert_eq!(D4::from_reflection_and_rotation(symmetry.is_reflection(), symmetry.quarter_turns()), symmetry);
+        }
+    }
+}
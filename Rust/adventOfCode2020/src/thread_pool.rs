@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+///
+/// Applies `work` to all items on up to `jobs` threads and passes the results to `consume` in the order of the items.
+/// Results are consumed as soon as the results of all earlier items are available.
+/// With a single job, the items are processed one after the other on the calling thread.
+///
+pub fn run_in_order<T, R, W, C>(items: Vec<T>, jobs: usize, work: W, mut consume: C)
+    where T: Send + 'static,
+          R: Send + 'static,
+          W: Fn(T) -> R + Send + Sync + 'static,
+          C: FnMut(R){
+    if jobs <= 1 || items.len() <= 1{
+        for item in items{
+            consume(work(item));
+        }
+        return;
+    }
+
+    let item_count = items.len();
+    let queue = Arc::new(Mutex::new(items.into_iter().enumerate()));
+    let work = Arc::new(work);
+    let (sender, receiver) = mpsc::channel();
+    let workers: Vec<thread::JoinHandle<()>> = (0..jobs.min(item_count))
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let work = Arc::clone(&work);
+            let sender = sender.clone();
+            thread::spawn(move || loop{
+                let next_item = queue.lock().unwrap().next();
+                match next_item{
+                    Some((index, item)) => {
+                        if sender.send((index, work(item))).is_err(){
+                            return;
+                        }
+                    },
+                    None => return,
+                }
+            })
+        })
+        .collect();
+    drop(sender);
+
+    let mut pending_results: HashMap<usize, R> = HashMap::new();
+    let mut next_index = 0;
+    for (index, result) in receiver{
+        pending_results.insert(index, result);
+        while let Some(result) = pending_results.remove(&next_index){
+            consume(result);
+            next_index += 1;
+        }
+    }
+
+    for worker in workers{
+        if let Err(panic) = worker.join(){
+            std::panic::resume_unwind(panic);
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod thread_pool_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[rstest]
+    #[case(1)]
+    #[case(3)]
+    #[case(8)]
+    #[case(40)]
+    fn results_are_consumed_in_order(#[case] jobs: usize) {
+        let items: Vec<u64> = (0..20).collect();
+        let mut results = vec![];
+        run_in_order(items, jobs, |item| {
+            thread::sleep(Duration::from_millis((20 - item) % 7));
+            item * item
+        }, |result| results.push(result));
+        assert_eq!(results, (0..20).map(|item| item * item).collect::<Vec<u64>>());
+    }
+}
@@ -32,7 +32,7 @@ function inputFromFile(path: string): string {
 }
 
 function puzzlePath(day: number): string{
-    let basePath = path.join(__dirname, '..', '..', 'Input');
+    let basePath = path.join(__dirname, '..', '..', 'Input', '2020');
     let filename = "Day" + day + ".txt";
     return path.join(basePath, filename);
 }